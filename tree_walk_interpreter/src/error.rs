@@ -1,16 +1,68 @@
+use std::fmt::{Display, Formatter};
 use std::num::ParseFloatError;
 
 use thiserror::Error;
 
+use crate::interpreter::limits::Limit;
+use crate::linter::Case;
+
 pub type LoxResult<T> = Result<T, LoxError>;
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Error, Clone, Debug, PartialEq)]
 pub enum LoxError {
-    #[error("{0:?}")]
+    #[error("{0}")]
     ScannerError(#[from] ScannerError),
 
     #[error("{0:?}")]
     LinterError(#[from] LinterError),
+
+    #[error("{0}")]
+    ParserError(#[from] ParserError),
+
+    #[error("{0}")]
+    ResolverError(#[from] ResolverError),
+
+    /// Raised while running the program, along with the Lox call stack at the time.
+    #[error("{0}\n{1}")]
+    RuntimeError(RuntimeError, StackTrace),
+
+    /// Every error found before the program could run, a script is scanned, parsed and resolved
+    /// as far as possible so they can all be fixed at once.
+    #[error("{}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("\n"))]
+    CompileErrors(Vec<LoxError>),
+
+    #[error("{0}")]
+    ReplError(#[from] ReplError),
+
+    /// The script itself couldn't be read, the io error is kept as a message so that errors stay
+    /// cloneable.
+    #[error("Unable to read {0}: {1}")]
+    IoError(String, String),
+
+    /// A REPL or linter config file that isn't valid TOML or has unknown settings.
+    #[error("Invalid config file {0}: {1}")]
+    InvalidConfig(String, String),
+}
+
+impl From<RuntimeError> for LoxError {
+    /// The stack trace is filled in by the interpreter as the error unwinds.
+    fn from(e: RuntimeError) -> Self {
+        LoxError::RuntimeError(e, StackTrace::default())
+    }
+}
+
+impl LoxError {
+    /// Scanner, parser and resolver errors are found before a single line of the program runs.
+    pub fn is_compile_error(&self) -> bool {
+        matches!(
+            self,
+            LoxError::ScannerError(_)
+                | LoxError::ParserError(_)
+                | LoxError::ResolverError(_)
+                | LoxError::CompileErrors(_)
+        )
+    }
 }
 
 #[derive(Error, Clone, Debug, PartialEq)]
@@ -19,6 +71,8 @@ pub enum ScannerError {
     UnexpectedCharacter(usize, usize, char),
     #[error("Error at {0}:{1} String was unterminated")]
     UnterminatedString(usize, usize),
+    #[error("Error at {0}:{1} Block comment was unterminated")]
+    UnterminatedBlockComment(usize, usize),
     #[error("Error at {0}:{1} String contained an unknown escape sequence: \"{2}\"")]
     UnknownStringEscapeSequence(usize, usize, String),
 
@@ -26,8 +80,219 @@ pub enum ScannerError {
     UnableToParseNumber(usize, usize, ParseFloatError),
 }
 
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum ParserError {
+    #[error("Error at {0}:{1} at '{2}': {3}")]
+    UnexpectedToken(usize, usize, String, String),
+    #[error("Error at {0}:{1} at end: {2}")]
+    UnexpectedEof(usize, usize, String),
+    #[error("Error at {0}:{1} at '{2}': Invalid assignment target.")]
+    InvalidAssignmentTarget(usize, usize, String),
+    #[error("Error at {0}:{1} at '{2}': Can't have more than 255 arguments.")]
+    TooManyArguments(usize, usize, String),
+    #[error("Error at {0}:{1} at '{2}': Can't have more than 255 parameters.")]
+    TooManyParameters(usize, usize, String),
+}
+
+impl ParserError {
+    /// Where in the source the error is.
+    pub fn span(&self) -> (usize, usize) {
+        match *self {
+            ParserError::UnexpectedToken(line, column, ..)
+            | ParserError::UnexpectedEof(line, column, _)
+            | ParserError::InvalidAssignmentTarget(line, column, _)
+            | ParserError::TooManyArguments(line, column, _)
+            | ParserError::TooManyParameters(line, column, _) => (line, column),
+        }
+    }
+}
+
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum ResolverError {
+    #[error("Error at {0}:{1} at '{2}': Can't read local variable in its own initializer.")]
+    ReadInOwnInitializer(usize, usize, String),
+    #[error("Error at {0}:{1} at '{2}': Already a variable with this name in this scope.")]
+    AlreadyDeclared(usize, usize, String),
+    #[error("Error at {0}:{1} at 'return': Can't return from top-level code.")]
+    TopLevelReturn(usize, usize),
+    #[error("Error at {0}:{1} at 'return': Can't return a value from an initializer.")]
+    ReturnValueFromInitializer(usize, usize),
+    #[error("Error at {0}:{1} at 'this': Can't use 'this' outside of a class.")]
+    ThisOutsideClass(usize, usize),
+    #[error("Error at {0}:{1} at '{2}': A class can't inherit from itself.")]
+    InheritFromSelf(usize, usize, String),
+    #[error("Error at {0}:{1} at 'super': Can't use 'super' outside of a class.")]
+    SuperOutsideClass(usize, usize),
+    #[error("Error at {0}:{1} at 'super': Can't use 'super' in a class with no superclass.")]
+    SuperWithoutSuperclass(usize, usize),
+}
+
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum RuntimeError {
+    #[error("Undefined variable '{2}'.")]
+    UndefinedVariable(usize, usize, String),
+    #[error("Operand must be a number.")]
+    OperandMustBeNumber(usize, usize),
+    #[error("Operands must be numbers.")]
+    OperandsMustBeNumbers(usize, usize),
+    #[error("Operands must be two numbers or two strings.")]
+    OperandsMustBeNumbersOrStrings(usize, usize),
+    #[error("Can only call functions and classes.")]
+    NotCallable(usize, usize),
+    #[error("Expected {2} arguments but got {3}.")]
+    ArityMismatch(usize, usize, usize, usize),
+    #[error("Only instances have properties.")]
+    OnlyInstancesHaveProperties(usize, usize),
+    #[error("Only instances have fields.")]
+    OnlyInstancesHaveFields(usize, usize),
+    #[error("Undefined property '{2}'.")]
+    UndefinedProperty(usize, usize, String),
+    #[error("Superclass must be a class.")]
+    SuperclassMustBeAClass(usize, usize),
+    #[error("Stack overflow.")]
+    StackOverflow(usize, usize),
+    #[error("Error in native function '{2}': {3}")]
+    NativeFunction(usize, usize, String, NativeError),
+    #[error("Exceeded the {2} limit.")]
+    LimitExceeded(usize, usize, Limit),
+    #[error("Interrupted.")]
+    Interrupted(usize, usize),
+}
+
+impl RuntimeError {
+    /// Where in the source the failing expression is.
+    pub fn span(&self) -> (usize, usize) {
+        match *self {
+            RuntimeError::UndefinedVariable(line, column, ..)
+            | RuntimeError::OperandMustBeNumber(line, column)
+            | RuntimeError::OperandsMustBeNumbers(line, column)
+            | RuntimeError::OperandsMustBeNumbersOrStrings(line, column)
+            | RuntimeError::NotCallable(line, column)
+            | RuntimeError::ArityMismatch(line, column, ..)
+            | RuntimeError::OnlyInstancesHaveProperties(line, column)
+            | RuntimeError::OnlyInstancesHaveFields(line, column)
+            | RuntimeError::UndefinedProperty(line, column, ..)
+            | RuntimeError::SuperclassMustBeAClass(line, column)
+            | RuntimeError::StackOverflow(line, column)
+            | RuntimeError::NativeFunction(line, column, ..)
+            | RuntimeError::LimitExceeded(line, column, _)
+            | RuntimeError::Interrupted(line, column) => (line, column),
+        }
+    }
+}
+
+/// Failure of a host function called from Lox, the interpreter adds where it was called from.
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum NativeError {
+    #[error("Expected {1} for argument {0} but got {2}.")]
+    ArgumentType(usize, &'static str, String),
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// One call on the Lox call stack, and the line that was executing in it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackFrame {
+    /// Name of the function, or `None` for the top level script.
+    pub function: Option<String>,
+    pub line: usize,
+}
+
+impl Display for StackFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.function {
+            Some(function) => write!(f, "[line {}] in {}()", self.line, function),
+            None => write!(f, "[line {}] in script", self.line),
+        }
+    }
+}
+
+/// The Lox call stack a [RuntimeError] unwound through, innermost call first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StackTrace(pub Vec<StackFrame>);
+
+impl StackTrace {
+    /// Deep recursion produces huge traces, only this many frames from each end are printed.
+    const PRINTED_FRAMES: usize = 10;
+}
+
+impl Display for StackTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let frames = &self.0;
+        let lines: Vec<String> = if frames.len() <= 2 * StackTrace::PRINTED_FRAMES {
+            frames.iter().map(|frame| frame.to_string()).collect()
+        } else {
+            let innermost = &frames[..StackTrace::PRINTED_FRAMES];
+            let outermost = &frames[frames.len() - StackTrace::PRINTED_FRAMES..];
+            let skipped = frames.len() - 2 * StackTrace::PRINTED_FRAMES;
+            innermost
+                .iter()
+                .map(|frame| frame.to_string())
+                .chain(std::iter::once(format!("... {} more frames", skipped)))
+                .chain(outermost.iter().map(|frame| frame.to_string()))
+                .collect()
+        };
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// A REPL meta-command (a line starting with `:`) that couldn't be run.
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum ReplError {
+    #[error("Unknown command ':{0}', type :help for a list of commands.")]
+    UnknownCommand(String),
+    #[error("The :{0} command expects {1}.")]
+    MissingArgument(String, &'static str),
+}
+
 #[derive(Error, Clone, Debug, PartialEq)]
 pub enum LinterError {
     #[error("Linter Error {0}:{1} More than one space detected, standard style is to only have one space")]
     DoubleSpaceDetected(usize, usize),
+    #[error("Linter Error {0}:{1} Tab detected, standard style is to indent with spaces")]
+    TabDetected(usize, usize),
+    #[error("Linter Error {0}:{1} Line ends with {2} but earlier lines end with {3}")]
+    InconsistentLineEnding(usize, usize, &'static str, &'static str),
+    #[error("Linter Error {0}:{1} Trailing whitespace detected")]
+    TrailingWhitespace(usize, usize),
+    #[error("Linter Error {0}:{1} Indented by {2} spaces, standard style is to indent by multiples of {3}")]
+    IndentationWidth(usize, usize, usize, usize),
+    #[error("Linter Error {0}:{1} Local variable '{2}' is never used")]
+    UnusedVariable(usize, usize, String),
+    #[error("Linter Error {0}:{1} Parameter '{2}' is never used")]
+    UnusedParameter(usize, usize, String),
+    #[error("Linter Error {0}:{1} '{2}' shadows the local variable declared at {3}:{4}")]
+    ShadowedVariable(usize, usize, String, usize, usize),
+    #[error("Linter Error {0}:{1} Code after this return is unreachable")]
+    UnreachableCode(usize, usize),
+    #[error("Linter Error {0}:{1} '{2}' is assigned to itself")]
+    SelfAssignment(usize, usize, String),
+    #[error("Linter Error {0}:{1} Comparison is always {2}")]
+    ConstantComparison(usize, usize, bool),
+    #[error("Linter Error {0}:{1} Comparing '{2}' with itself is always {3}, unless it is NaN")]
+    SelfComparison(usize, usize, String, bool),
+    /// The kind of name, the name, the convention it breaks and the name rewritten to follow it.
+    #[error("Linter Error {0}:{1} {2} '{3}' should be {4}, such as '{5}'")]
+    NamingConvention(usize, usize, &'static str, String, Case, String),
+}
+
+impl LinterError {
+    /// Where in the source the finding is.
+    pub fn span(&self) -> (usize, usize) {
+        match *self {
+            LinterError::DoubleSpaceDetected(line, column)
+            | LinterError::TabDetected(line, column)
+            | LinterError::InconsistentLineEnding(line, column, ..)
+            | LinterError::TrailingWhitespace(line, column)
+            | LinterError::IndentationWidth(line, column, ..)
+            | LinterError::UnusedVariable(line, column, _)
+            | LinterError::UnusedParameter(line, column, _)
+            | LinterError::ShadowedVariable(line, column, ..)
+            | LinterError::UnreachableCode(line, column)
+            | LinterError::SelfAssignment(line, column, _)
+            | LinterError::ConstantComparison(line, column, _)
+            | LinterError::SelfComparison(line, column, ..)
+            | LinterError::NamingConvention(line, column, ..) => (line, column),
+        }
+    }
 }
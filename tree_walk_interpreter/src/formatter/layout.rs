@@ -0,0 +1,162 @@
+//! Turns the formatter's tokens into lines, wrapping the ones that are too long.
+
+use crate::formatter::{INDENT_WIDTH, MAX_WIDTH};
+use crate::scanner::tokens::TokenType;
+
+/// What goes before a token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum Separator {
+    None,
+    Space,
+    Newline,
+    /// A line break with an empty line after it.
+    BlankLine,
+}
+
+/// A token to print.
+#[derive(Clone, Debug)]
+pub(super) struct Item {
+    pub text: String,
+    pub separator: Separator,
+    /// Indentation level of the line, if the token starts one.
+    pub indent: usize,
+    /// `None` for text printed as it was written, such as a statement with errors in it.
+    pub token_type: Option<TokenType>,
+}
+
+impl Item {
+    fn starts_line(&self) -> bool {
+        matches!(self.separator, Separator::Newline | Separator::BlankLine)
+    }
+}
+
+/// The items printed, ending with a line break unless there aren't any.
+pub(super) fn render(items: &[Item], newline: &str) -> String {
+    let mut output = String::new();
+    for line in lines(items) {
+        if line[0].separator == Separator::BlankLine {
+            output.push_str(newline);
+        }
+        for (indent, segment) in wrap(line) {
+            output.push_str(&" ".repeat(indent * INDENT_WIDTH));
+            output.push_str(&join(segment));
+            output.push_str(newline);
+        }
+    }
+    output
+}
+
+fn lines(items: &[Item]) -> Vec<&[Item]> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for (index, item) in items.iter().enumerate() {
+        if index > start && item.starts_line() {
+            lines.push(&items[start..index]);
+            start = index;
+        }
+    }
+    if start < items.len() {
+        lines.push(&items[start..]);
+    }
+    lines
+}
+
+/// The items of a line with the spaces between them, the first one's separator is left out.
+fn join(items: &[Item]) -> String {
+    let mut text = String::new();
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 && item.separator == Separator::Space {
+            text.push(' ');
+        }
+        text.push_str(&item.text);
+    }
+    text
+}
+
+/// A parenthesised list on a line.
+struct Group {
+    open: usize,
+    /// `None` if the list is closed on a later line.
+    close: Option<usize>,
+    /// How many lists the group is inside of.
+    level: usize,
+    commas: Vec<usize>,
+}
+
+/// Splits a line that is too long after the opening parenthesis and commas of its outermost
+/// lists, such as the arguments of a call, indenting the list's items one level further.  Lines
+/// with comments in the middle, text spanning several lines or text printed as it was written
+/// are left as they are.
+fn wrap(line: &[Item]) -> Vec<(usize, &[Item])> {
+    let indent = line[0].indent;
+    let width = indent * INDENT_WIDTH + join(line).chars().count();
+    let unwrappable = line
+        .iter()
+        .enumerate()
+        .any(|(index, item)| match &item.token_type {
+            None => true,
+            Some(TokenType::Comment | TokenType::BlockComment) => index + 1 < line.len(),
+            Some(_) => item.text.contains('\n'),
+        });
+    if width <= MAX_WIDTH || unwrappable {
+        return vec![(indent, line)];
+    }
+
+    let groups: Vec<Group> = groups(line)
+        .into_iter()
+        .filter(|group| !group.commas.is_empty())
+        .collect();
+    let outermost = match groups.iter().map(|group| group.level).min() {
+        Some(level) => level,
+        None => return vec![(indent, line)],
+    };
+    let mut breaks: Vec<(usize, usize)> = Vec::new();
+    for group in groups.iter().filter(|group| group.level == outermost) {
+        breaks.push((group.open + 1, indent + 1));
+        breaks.extend(group.commas.iter().map(|&comma| (comma + 1, indent + 1)));
+        if let Some(close) = group.close {
+            breaks.push((close, indent));
+        }
+    }
+    breaks.sort_unstable();
+
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut segment_indent = indent;
+    for (index, next_indent) in breaks {
+        segments.push((segment_indent, &line[start..index]));
+        start = index;
+        segment_indent = next_indent;
+    }
+    segments.push((segment_indent, &line[start..]));
+    segments
+}
+
+fn groups(line: &[Item]) -> Vec<Group> {
+    let mut groups = Vec::new();
+    let mut open: Vec<Group> = Vec::new();
+    for (index, item) in line.iter().enumerate() {
+        match item.token_type {
+            Some(TokenType::LeftParen) => open.push(Group {
+                open: index,
+                close: None,
+                level: open.len(),
+                commas: Vec::new(),
+            }),
+            Some(TokenType::RightParen) => {
+                if let Some(mut group) = open.pop() {
+                    group.close = Some(index);
+                    groups.push(group);
+                }
+            }
+            Some(TokenType::Comma) => {
+                if let Some(group) = open.last_mut() {
+                    group.commas.push(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    groups.extend(open);
+    groups
+}
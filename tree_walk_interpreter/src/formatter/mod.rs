@@ -0,0 +1,393 @@
+//! Reprints Lox programs in a canonical style: four space indentation, one statement per line,
+//! braces on the line of the statement they belong to and single spaces around operators.
+//!
+//! The formatter works on the tokens rather than the syntax tree so that comments, which the
+//! parser never sees, stay where they were written.  A line comment on the same line as code
+//! stays at the end of that line, any other comment goes on a line of its own.  Blank lines
+//! between statements are kept, though never more than one in a row.
+
+use std::ops::Range;
+
+use crate::error::{LoxError, LoxResult};
+use crate::parser::parse;
+use crate::scanner::scan_with_whitespace;
+use crate::scanner::tokens::{LoxToken, TokenType};
+use crate::session::scan_and_parse;
+
+use layout::{Item, Separator};
+use tree::{LineStarts, Piece, Statement};
+
+mod layout;
+mod tree;
+
+#[cfg(test)]
+mod tests;
+
+/// Spaces per level of indentation.
+pub const INDENT_WIDTH: usize = 4;
+
+/// Lines longer than this are wrapped when they have an argument or parameter list to wrap.
+pub const MAX_WIDTH: usize = 100;
+
+/// The source formatted, or the errors that stop it from being a valid program.  Formatting
+/// formatted source doesn't change it.
+pub fn format_source(source: &str) -> LoxResult<String> {
+    scan_and_parse(source, parse)?;
+    let tokens = scan_with_whitespace(source, false);
+    let pieces = tree::pieces(source, &LineStarts::new(source), &tokens);
+    Ok(layout::render(
+        &items(source, &pieces, 0..pieces.len(), &[], 0),
+        "\n",
+    ))
+}
+
+/// The source with just the statements in the byte `range` formatted, for an editor's "format
+/// selection".  The range is widened to whole statements on whole lines, and a statement inside
+/// a block is indented to the block's depth.  A range covering the whole source formats all of
+/// it, including the comments before the first statement and after the last.
+///
+/// Unlike [format_source] this never fails: statements with scanner or parser errors in them are
+/// left exactly as they were written, indentation included, and so is the source's line ending.
+pub fn format_range(source: &str, range: Range<usize>) -> String {
+    let tokens = scan_with_whitespace(source, false);
+    let lines = LineStarts::new(source);
+    let pieces = tree::pieces(source, &lines, &tokens);
+    let statements = tree::statements(&pieces);
+
+    // Text that didn't scan shows up as pieces without a token.
+    let mut errors: Vec<usize> = pieces
+        .iter()
+        .filter(|p| p.token.is_none())
+        .map(|p| p.start)
+        .collect();
+    if let Err(parser_errors) = parse(&tokens) {
+        errors.extend(parser_errors.iter().filter_map(|e| match e {
+            LoxError::ParserError(e) => {
+                let (line, column) = e.span();
+                Some(lines.offset(line, column))
+            }
+            _ => None,
+        }));
+    }
+    let verbatim = tree::invalid(&statements, &pieces, errors);
+    let newline = match source.find('\n') {
+        Some(i) if source[..i].ends_with('\r') => "\r\n",
+        _ => "\n",
+    };
+
+    if range.start == 0 && range.end >= source.len() {
+        return layout::render(
+            &items(source, &pieces, 0..pieces.len(), &verbatim, 0),
+            newline,
+        );
+    }
+    let selection = match select(&statements, &pieces, &lines, range) {
+        Some(selection) => selection,
+        None => return source.to_string(),
+    };
+    // Inside a statement with errors, which is left as it is.
+    if verbatim
+        .iter()
+        .any(|r| r.start < selection.pieces.start && selection.pieces.start < r.end)
+    {
+        return source.to_string();
+    }
+    let items = items(
+        source,
+        &pieces,
+        selection.pieces.clone(),
+        &verbatim,
+        selection.depth,
+    );
+    let formatted = layout::render(&items, newline);
+    format!(
+        "{}{}{}",
+        &source[..selection.bytes.start],
+        formatted.strip_suffix(newline).unwrap_or(&formatted),
+        &source[selection.bytes.end..]
+    )
+}
+
+/// Statements chosen to be formatted.
+struct Selection {
+    pieces: Range<usize>,
+    /// The whole lines they are on.
+    bytes: Range<usize>,
+    /// How many blocks they are inside of.
+    depth: usize,
+}
+
+/// The statements overlapping the range, from the innermost block the range is inside of.  The
+/// lines they are on mustn't have any other code on them, so neighbouring statements are added
+/// and a statement sharing a line with the braces of its block is swapped for the statement the
+/// block belongs to.
+fn select(
+    statements: &[Statement],
+    pieces: &[Piece],
+    lines: &LineStarts,
+    range: Range<usize>,
+) -> Option<Selection> {
+    // An empty range still selects the statement it is in.
+    let range = range.start..range.end.max(range.start + 1);
+    let overlaps = |bytes: Range<usize>| bytes.start < range.end && range.start < bytes.end;
+
+    let mut enclosing: Vec<(&[Statement], usize)> = Vec::new();
+    let mut list = statements;
+    let (mut first, mut last) = loop {
+        let overlapping: Vec<usize> = (0..list.len())
+            .filter(|&i| overlaps(list[i].bytes(pieces)))
+            .collect();
+        let (&first, &last) = (overlapping.first()?, overlapping.last()?);
+        let inner = list[first].blocks.iter().find(|b| {
+            let inside = b.inside(pieces);
+            inside.start <= range.start && range.end <= inside.end
+        });
+        match inner {
+            Some(block) if first == last => {
+                enclosing.push((list, first));
+                list = &block.statements;
+            }
+            _ => break (first, last),
+        }
+    };
+
+    loop {
+        let start = list[first].bytes(pieces).start;
+        let end = list[last].bytes(pieces).end;
+        let mut bytes = lines.line_start(start)..lines.line_end(end);
+        // Comments and strings spanning several lines are kept whole.
+        while let Some(piece) = pieces
+            .iter()
+            .find(|p| p.start < bytes.start && bytes.start < p.end)
+        {
+            bytes.start = lines.line_start(piece.start);
+        }
+        while let Some(piece) = pieces
+            .iter()
+            .find(|p| p.start < bytes.end && bytes.end < p.end)
+        {
+            bytes.end = lines.line_end(piece.end);
+        }
+
+        let code = |within: Range<usize>| {
+            pieces
+                .iter()
+                .any(|p| p.is_significant() && within.contains(&p.start))
+        };
+        let code_before = code(bytes.start..start);
+        let code_after = code(end..bytes.end);
+        if code_before && first > 0 {
+            first -= 1;
+        } else if code_after && last + 1 < list.len() {
+            last += 1;
+        } else if code_before || code_after {
+            let (parent, index) = enclosing.pop()?;
+            list = parent;
+            first = index;
+            last = index;
+        } else {
+            let first_piece = pieces.iter().position(|p| p.start >= bytes.start)?;
+            let end_piece = pieces
+                .iter()
+                .rposition(|p| p.end <= bytes.end)
+                .map_or(first_piece, |i| i + 1);
+            return Some(Selection {
+                pieces: first_piece..end_piece.max(first_piece),
+                bytes,
+                depth: enclosing.len(),
+            });
+        }
+    }
+}
+
+/// The pieces to print, comments included, each with the separator that goes before it.  The
+/// `verbatim` ranges of pieces are printed as they were written, as a single item.
+fn items(
+    source: &str,
+    pieces: &[Piece],
+    range: Range<usize>,
+    verbatim: &[Range<usize>],
+    depth: usize,
+) -> Vec<Item> {
+    let mut items: Vec<Item> = Vec::new();
+    let mut state = State {
+        braces: depth,
+        ..State::default()
+    };
+    let mut newlines = 0;
+    let mut index = range.start;
+    while index < range.end {
+        let piece = pieces[index];
+        if let Some(token) = piece.token.filter(|t| t.is_whitespace()) {
+            if token.token_type == TokenType::Linefeed {
+                newlines += 1;
+            }
+            index += 1;
+            continue;
+        }
+
+        let separator = state.separator(piece.token, newlines);
+        let closing = piece.token_type() == Some(&TokenType::RightBrace);
+        let mut indent = state.braces.saturating_sub(usize::from(closing))
+            + usize::from(state.in_statement && !closing);
+        let verbatim_end = verbatim
+            .iter()
+            .find(|r| r.start == index)
+            .map(|r| r.end.min(range.end));
+        let end = verbatim_end.unwrap_or(index + 1);
+        let (text, token_type) = match piece.token {
+            Some(token) if verbatim_end.is_none() => (text(token), Some(token.token_type.clone())),
+            _ => {
+                // Text starting a line keeps the indentation it was written with.
+                let line_start = source[..piece.start].rfind('\n').map_or(0, |i| i + 1);
+                let starts_line = items.is_empty()
+                    || matches!(separator, Separator::Newline | Separator::BlankLine);
+                let start = if starts_line && source[line_start..piece.start].trim().is_empty() {
+                    indent = 0;
+                    line_start
+                } else {
+                    piece.start
+                };
+                // Text that failed to scan can run to the end of the source, line ending included.
+                let text = source[start..pieces[end - 1].end].trim_end_matches(['\r', '\n']);
+                (text.to_string(), None)
+            }
+        };
+        items.push(Item {
+            text,
+            separator,
+            indent,
+            token_type,
+        });
+        for token in pieces[index..end].iter().filter_map(|p| p.token) {
+            if !token.is_whitespace() {
+                state.advance(token);
+            }
+        }
+        // Whatever the statement left unbalanced, the next one starts afresh.
+        if verbatim_end.is_some() {
+            state.parens = 0;
+            state.in_statement = false;
+            state.after_statement = true;
+        }
+        index = end;
+        newlines = 0;
+    }
+    items
+}
+
+/// Line comments lose trailing whitespace, everything else is printed as written.
+fn text(token: &LoxToken) -> String {
+    match token.token_type {
+        TokenType::Comment => token.lexeme.trim_end().to_string(),
+        _ => token.lexeme.clone(),
+    }
+}
+
+/// Where the formatter is in the program, as far as the tokens so far tell.
+#[derive(Default)]
+struct State {
+    /// The token before, comments included.
+    previous: Option<TokenType>,
+    /// Whether the previous token ends an operand, making a `-` after it a binary minus.
+    previous_operand: bool,
+    /// Whether the previous token is a prefix `-` or `!`.
+    previous_unary: bool,
+    braces: usize,
+    parens: usize,
+    /// Whether the next token continues a statement, rather than starting one.
+    in_statement: bool,
+    /// Whether the token before, leaving out comments, ended a statement or a brace.
+    after_statement: bool,
+}
+
+impl State {
+    /// What goes between the previous token and this one, which had `newlines` line breaks before
+    /// it in the source.
+    fn separator(&self, token: Option<&LoxToken>, newlines: usize) -> Separator {
+        use TokenType::*;
+
+        let previous = match &self.previous {
+            Some(previous) => previous,
+            None => return Separator::None,
+        };
+        // Where a line break goes anyway, a blank line in the source is kept.
+        let line_break = if newlines > 1 {
+            Separator::BlankLine
+        } else {
+            Separator::Newline
+        };
+        // Text that didn't scan stays on the line it was on, like a comment.
+        let token = match token {
+            Some(token) => token,
+            None if newlines == 0 => return Separator::Space,
+            None => return line_break,
+        };
+        let current = &token.token_type;
+
+        if token.is_comment() {
+            return if newlines == 0 {
+                Separator::Space
+            } else if *previous == LeftBrace {
+                Separator::Newline
+            } else {
+                line_break
+            };
+        }
+        match (previous, current) {
+            (Comment, RightBrace) => Separator::Newline,
+            (Comment, _) => line_break,
+            (BlockComment, RightBrace) if newlines > 0 => Separator::Newline,
+            (BlockComment, _) if newlines > 0 => line_break,
+            (BlockComment, Else) => Separator::Space,
+            // A comment after the end of a statement stays on its line, like a line comment.
+            (BlockComment, _) if self.after_statement => Separator::Newline,
+            (BlockComment, _) => Separator::Space,
+            (LeftBrace, RightBrace) => Separator::None,
+            (LeftBrace, _) | (_, RightBrace) => Separator::Newline,
+            (RightBrace | Semicolon, Else) => Separator::Space,
+            (RightBrace, _) => line_break,
+            (Semicolon, _) if self.parens == 0 => line_break,
+            // The clauses of a `for`.
+            (Semicolon, Semicolon | RightParen) => Separator::None,
+            (_, RightParen | Comma | Semicolon | Dot) => Separator::None,
+            (LeftParen | Dot, _) => Separator::None,
+            _ if self.previous_unary => Separator::None,
+            // A call, or the parameters of a function or method.
+            (Identifier | RightParen, LeftParen) => Separator::None,
+            _ => Separator::Space,
+        }
+    }
+
+    fn advance(&mut self, token: &LoxToken) {
+        use TokenType::*;
+
+        match token.token_type {
+            LeftParen => self.parens += 1,
+            RightParen => self.parens = self.parens.saturating_sub(1),
+            LeftBrace => self.braces += 1,
+            RightBrace => self.braces = self.braces.saturating_sub(1),
+            _ => {}
+        }
+        if token.is_comment() {
+            self.previous = Some(token.token_type.clone());
+            return;
+        }
+        self.previous_unary = match token.token_type {
+            Bang => true,
+            Minus => !self.previous_operand,
+            _ => false,
+        };
+        self.in_statement = match token.token_type {
+            Semicolon => self.parens > 0,
+            LeftBrace | RightBrace => false,
+            _ => true,
+        };
+        self.after_statement = !self.in_statement;
+        self.previous_operand = matches!(
+            token.token_type,
+            Identifier | String(_) | Number(_) | RightParen | True | False | Nil | This
+        );
+        self.previous = Some(token.token_type.clone());
+    }
+}
@@ -0,0 +1,79 @@
+//! Formats every script under `lox/test` that is a valid program, checking that formatting
+//! doesn't change what the program does and that formatting again doesn't change anything.  The
+//! scripts with errors in them are formatted as far as they can be.
+
+use std::fs::{read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+
+use crate::formatter::{format_range, format_source};
+use crate::parser::parse;
+use crate::session::scan_and_parse;
+
+fn lox_scripts(directory: &Path, scripts: &mut Vec<PathBuf>) {
+    for entry in read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            lox_scripts(&path, scripts);
+        } else if path.extension().is_some_and(|extension| extension == "lox") {
+            scripts.push(path);
+        }
+    }
+}
+
+/// The program printed as S-expressions, which leave out everything formatting changes.
+fn syntax_tree(source: &str) -> Option<Vec<String>> {
+    let statements = scan_and_parse(source, parse).ok()?;
+    Some(statements.iter().map(|s| s.to_string()).collect())
+}
+
+/// Formatting a script with errors as far as it can be has to be idempotent too.
+fn check_tolerant(source: &str) -> Option<String> {
+    let formatted = format_range(source, 0..source.len());
+    (format_range(&formatted, 0..formatted.len()) != formatted)
+        .then(|| "formatting around errors isn't idempotent".to_string())
+}
+
+#[test]
+fn formatting_lox_test() {
+    let test_directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("../lox/test");
+    let mut scripts = Vec::new();
+    lox_scripts(&test_directory, &mut scripts);
+    scripts.sort();
+    assert!(!scripts.is_empty(), "no scripts in {:?}", test_directory);
+
+    let mut formatted_scripts = 0;
+    let failures: Vec<String> = scripts
+        .iter()
+        .filter_map(|script| {
+            let name = script.strip_prefix(&test_directory).unwrap().display();
+            let source = read_to_string(script).unwrap();
+            let tree = syntax_tree(&source);
+            let formatted = match (format_source(&source), &tree) {
+                (Ok(formatted), Some(_)) => formatted,
+                (Err(_), None) => {
+                    return check_tolerant(&source).map(|f| format!("{}: {}", name, f))
+                }
+                (Ok(_), None) => return Some(format!("{}: formatted an invalid program", name)),
+                (Err(e), Some(_)) => return Some(format!("{}: {}", name, e)),
+            };
+            formatted_scripts += 1;
+            if format_range(&source, 0..source.len()) != formatted {
+                return Some(format!("{}: formatting the whole range differs", name));
+            }
+            if syntax_tree(&formatted) != tree {
+                return Some(format!("{}: formatting changed the program", name));
+            }
+            match format_source(&formatted) {
+                Ok(again) if again == formatted => None,
+                _ => Some(format!("{}: formatting isn't idempotent", name)),
+            }
+        })
+        .collect();
+
+    assert!(
+        formatted_scripts > 200,
+        "only {} scripts formatted",
+        formatted_scripts
+    );
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,121 @@
+use pretty_assertions::assert_eq;
+
+use crate::formatter::format_source;
+
+mod lox_test;
+mod range;
+
+fn assert_formats(source: &str, expected: &str) {
+    assert_eq!(format_source(source).unwrap(), expected);
+    assert_eq!(format_source(expected).unwrap(), expected);
+}
+
+#[test]
+fn empty_source() {
+    assert_formats("", "");
+    assert_formats("\n\n  \n", "");
+}
+
+#[test]
+fn spacing() {
+    assert_formats(
+        "var  a=1+2*-3;print(a)  ;print !true==false;",
+        "var a = 1 + 2 * -3;\nprint (a);\nprint !true == false;\n",
+    );
+    assert_formats("print a - -b;print -(a);", "print a - -b;\nprint -(a);\n");
+    assert_formats(
+        "foo . bar ( 1 , 2 ) ( ) ;this.x=super.y;",
+        "foo.bar(1, 2)();\nthis.x = super.y;\n",
+    );
+}
+
+#[test]
+fn blocks() {
+    assert_formats(
+        "fun f(a,b){if(a<b){return a;}else{return b;}}",
+        "fun f(a, b) {\n    if (a < b) {\n        return a;\n    } else {\n        return b;\n    }\n}\n",
+    );
+    assert_formats(
+        "class B<A{init(){} method(){super.method();}}",
+        "class B < A {\n    init() {}\n    method() {\n        super.method();\n    }\n}\n",
+    );
+    assert_formats("{\n\n{}\n\n}", "{\n    {}\n}\n");
+}
+
+#[test]
+fn statements_without_braces() {
+    assert_formats(
+        "if (a)\n  print 1;\nelse\n  print 2;\nwhile (a) a = a - 1;",
+        "if (a) print 1; else print 2;\nwhile (a) a = a - 1;\n",
+    );
+    assert_formats(
+        "if (a) if (b) print 1; else { print 2; }",
+        "if (a) if (b) print 1; else {\n    print 2;\n}\n",
+    );
+    assert_formats(
+        "for(var i=0;i<3;i=i+1)print i;for(;;){}",
+        "for (var i = 0; i < 3; i = i + 1) print i;\nfor (;;) {}\n",
+    );
+}
+
+#[test]
+fn blank_lines() {
+    assert_formats(
+        "\n\nvar a;\n\n\n\nvar b;\nvar c;\n\n",
+        "var a;\n\nvar b;\nvar c;\n",
+    );
+}
+
+#[test]
+fn comments() {
+    assert_formats(
+        "// Header.\n\nvar a = 1; // Trailing.   \n{ // After a brace.\n// Own line.\nprint a;\n  // Before a brace.\n}",
+        "// Header.\n\nvar a = 1; // Trailing.\n{ // After a brace.\n    // Own line.\n    print a;\n    // Before a brace.\n}\n",
+    );
+    assert_formats(
+        "var a = /* inline */ 1;\n/* Own\n   lines. */\nprint a;",
+        "var a = /* inline */ 1;\n/* Own\n   lines. */\nprint a;\n",
+    );
+    assert_formats(
+        "var a = 1; /* After. */ print a;\n{ /* Brace. */ print a; /* End. */ }",
+        "var a = 1; /* After. */\nprint a;\n{ /* Brace. */\n    print a; /* End. */\n}\n",
+    );
+}
+
+#[test]
+fn comments_inside_statements() {
+    assert_formats(
+        "var a = 1 + // One.\n2;\nprint a;",
+        "var a = 1 + // One.\n    2;\nprint a;\n",
+    );
+}
+
+#[test]
+fn line_endings() {
+    assert_formats(
+        "var a;\r\n\r\n// Comment\r\nprint a;\r\n",
+        "var a;\n\n// Comment\nprint a;\n",
+    );
+}
+
+#[test]
+fn wrapping() {
+    let arguments: Vec<String> = (0..12).map(|i| format!("argument{}", i)).collect();
+    let source = format!("{{ print call({}, nested(1, 2)); }}", arguments.join(", "));
+    let mut expected = "{\n    print call(\n".to_string();
+    for argument in &arguments {
+        expected.push_str(&format!("        {},\n", argument));
+    }
+    expected.push_str("        nested(1, 2)\n    );\n}\n");
+    assert_formats(&source, &expected);
+
+    // Nothing to wrap at.
+    let long = format!("print {};\n", vec!["a"; 60].join(" + "));
+    assert_formats(&long, &long);
+}
+
+#[test]
+fn invalid_source() {
+    assert!(format_source("print 1").is_err());
+    assert!(format_source("var a = @;").is_err());
+}
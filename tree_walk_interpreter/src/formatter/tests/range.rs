@@ -0,0 +1,118 @@
+use pretty_assertions::assert_eq;
+
+use crate::formatter::format_range;
+
+/// Formats the statements around the first occurrence of `selected`.
+fn format_selection(source: &str, selected: &str) -> String {
+    let start = source.find(selected).unwrap();
+    format_range(source, start..start + selected.len())
+}
+
+#[test]
+fn whole_source() {
+    let source = "// Header.\nvar  a=1;\n\n\n{print a;}\n// Footer.\n";
+    assert_eq!(
+        format_range(source, 0..source.len()),
+        "// Header.\nvar a = 1;\n\n{\n    print a;\n}\n// Footer.\n"
+    );
+}
+
+#[test]
+fn selected_statements() {
+    let source = "var  a=1;\nvar  b=2;\nvar  c=3;\nvar  d=4;\n";
+    assert_eq!(
+        format_selection(source, "b=2;\nvar  c"),
+        "var  a=1;\nvar b = 2;\nvar c = 3;\nvar  d=4;\n"
+    );
+    // A cursor formats the statement it is in.
+    let cursor = source.find("c=").unwrap();
+    assert_eq!(
+        format_range(source, cursor..cursor),
+        "var  a=1;\nvar  b=2;\nvar c = 3;\nvar  d=4;\n"
+    );
+}
+
+#[test]
+fn statements_in_blocks() {
+    let source = "fun f() {\nvar  a=1;\n  if (a) {\n print  a;\n  }\n}\n";
+    assert_eq!(
+        format_selection(source, "print  a;"),
+        "fun f() {\nvar  a=1;\n  if (a) {\n        print a;\n  }\n}\n"
+    );
+    assert_eq!(
+        format_selection(source, "if (a)"),
+        "fun f() {\nvar  a=1;\n    if (a) {\n        print a;\n    }\n}\n"
+    );
+}
+
+#[test]
+fn whole_lines() {
+    // Sharing a line with another statement or with its block's braces pulls those in too.
+    let source = "var  a=1; var  b=2;\n{ print  a; }\nprint  b;\n";
+    assert_eq!(
+        format_selection(source, "b=2"),
+        "var a = 1;\nvar b = 2;\n{ print  a; }\nprint  b;\n"
+    );
+    assert_eq!(
+        format_selection(source, "print  a"),
+        "var  a=1; var  b=2;\n{\n    print a;\n}\nprint  b;\n"
+    );
+    // Comments on the lines are formatted along with the statement.
+    assert_eq!(
+        format_range("/* c */ print  1; // d   \n", 8..9),
+        "/* c */ print 1; // d\n"
+    );
+}
+
+#[test]
+fn nothing_selected() {
+    let source = "var  a=1;\n\n// Comment.\nvar  b=2;\n";
+    assert_eq!(format_selection(source, "Comment"), source);
+    assert_eq!(format_selection(source, "\n\n"), source);
+}
+
+#[test]
+fn statements_with_errors() {
+    let source = "var  a=1;\nprint (a +;\nvar  b=@ 2;\n{\n  print  a;\n  a =;\n}\nprint  é;";
+    assert_eq!(
+        format_range(source, 0..source.len()),
+        "var a = 1;\nprint (a +;\nvar  b=@ 2;\n{\n    print a;\n  a =;\n}\nprint  é;\n"
+    );
+    assert_eq!(format_selection(source, "b=@"), source);
+    // Even the indentation of a statement with errors is kept.
+    let source = "{\n\tprint  1;\n\t\tprint (;\n}\n";
+    assert_eq!(
+        format_selection(source, "{"),
+        "{\n    print 1;\n\t\tprint (;\n}\n"
+    );
+}
+
+#[test]
+fn unfinished_source() {
+    // Unclosed blocks and strings leave everything from where they start as it is.
+    let source = "print  1;\nfun f() {\n  print  2;\n";
+    assert_eq!(
+        format_range(source, 0..source.len()),
+        "print 1;\nfun f() {\n  print  2;\n"
+    );
+    assert_eq!(format_selection(source, "print  2"), source);
+    let source = "print  1;\nprint \"a\n  b;\n";
+    assert_eq!(
+        format_range(source, 0..source.len()),
+        "print 1;\nprint \"a\n  b;\n"
+    );
+    assert_eq!(format_range("print 1", 0..7), "print 1\n");
+}
+
+#[test]
+fn line_endings_are_kept() {
+    let source = "var  a=1;\r\n{\r\nprint  a;\r\n}\r\n";
+    assert_eq!(
+        format_selection(source, "print"),
+        "var  a=1;\r\n{\r\n    print a;\r\n}\r\n"
+    );
+    assert_eq!(
+        format_range(source, 0..source.len()),
+        "var a = 1;\r\n{\r\n    print a;\r\n}\r\n"
+    );
+}
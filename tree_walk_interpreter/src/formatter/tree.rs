@@ -0,0 +1,291 @@
+//! The source as a lossless list of pieces and the statements they make up, found from the
+//! tokens alone so that source with errors in it still has a shape.
+
+use std::ops::Range;
+
+use crate::error::LoxResult;
+use crate::scanner::tokens::{LoxToken, TokenType};
+
+/// A token and the bytes of the source it was scanned from, or text the scanner couldn't make
+/// sense of.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Piece<'a> {
+    pub token: Option<&'a LoxToken>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl<'a> Piece<'a> {
+    pub fn token_type(&self) -> Option<&'a TokenType> {
+        self.token.map(|t| &t.token_type)
+    }
+
+    /// Whether the piece is code, rather than whitespace or a comment.
+    pub fn is_significant(&self) -> bool {
+        self.token
+            .is_none_or(|t| !t.is_whitespace() && !t.is_comment())
+    }
+}
+
+/// Byte offsets of the start of each line, to find the tokens' `(line, column)` in the source.
+pub(super) struct LineStarts<'s> {
+    source: &'s str,
+    starts: Vec<usize>,
+}
+
+impl<'s> LineStarts<'s> {
+    pub fn new(source: &'s str) -> LineStarts<'s> {
+        let starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineStarts { source, starts }
+    }
+
+    /// Columns count bytes from the start of the line, starting at 1.
+    pub fn offset(&self, line: usize, column: usize) -> usize {
+        let mut offset = match self.starts.get(line.saturating_sub(1)) {
+            Some(&start) => (start + column.saturating_sub(1)).min(self.source.len()),
+            None => self.source.len(),
+        };
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    /// Start of the line the offset is on.
+    pub fn line_start(&self, offset: usize) -> usize {
+        match self.starts.binary_search(&offset) {
+            Ok(index) => self.starts[index],
+            Err(index) => self.starts[index - 1],
+        }
+    }
+
+    /// End of the line the offset is on, before its line ending.
+    pub fn line_end(&self, offset: usize) -> usize {
+        let end = self.source[offset..]
+            .find('\n')
+            .map_or(self.source.len(), |i| offset + i);
+        if end > offset && self.source[..end].ends_with('\r') {
+            end - 1
+        } else {
+            end
+        }
+    }
+}
+
+/// Every byte of the source in order, split into the scanned tokens (the `Eof` left out) and the
+/// text in between that failed to scan.
+pub(super) fn pieces<'a>(
+    source: &str,
+    lines: &LineStarts,
+    tokens: &'a [LoxResult<LoxToken>],
+) -> Vec<Piece<'a>> {
+    let mut pieces = Vec::new();
+    let mut end = 0;
+    for token in tokens.iter().filter_map(|t| t.as_ref().ok()) {
+        if token.token_type == TokenType::Eof {
+            continue;
+        }
+        let start = lines.offset(token.line, token.column).max(end);
+        if start > end {
+            pieces.push(Piece {
+                token: None,
+                start: end,
+                end: start,
+            });
+        }
+        end = (start + token.lexeme.len()).min(source.len());
+        pieces.push(Piece {
+            token: Some(token),
+            start,
+            end,
+        });
+    }
+    if end < source.len() {
+        pieces.push(Piece {
+            token: None,
+            start: end,
+            end: source.len(),
+        });
+    }
+    pieces
+}
+
+/// A statement, or a method in a class body, as the pieces from its first token up to its `;`
+/// or closing brace.  Comments before or after it aren't part of it.
+#[derive(Debug)]
+pub(super) struct Statement {
+    pub pieces: Range<usize>,
+    pub blocks: Vec<Block>,
+}
+
+/// The statements between a pair of braces.
+#[derive(Debug)]
+pub(super) struct Block {
+    pub open: usize,
+    /// `None` if the block is never closed.
+    pub close: Option<usize>,
+    pub statements: Vec<Statement>,
+}
+
+impl Statement {
+    pub fn bytes(&self, pieces: &[Piece]) -> Range<usize> {
+        pieces[self.pieces.start].start..pieces[self.pieces.end - 1].end
+    }
+}
+
+impl Block {
+    /// The bytes between the braces.
+    pub fn inside(&self, pieces: &[Piece]) -> Range<usize> {
+        let end = self
+            .close
+            .map_or_else(|| pieces.last().map_or(0, |p| p.end), |c| pieces[c].start);
+        pieces[self.open].end..end
+    }
+}
+
+/// The top level statements.  A `}` without a `{` is a statement of its own.
+pub(super) fn statements(pieces: &[Piece]) -> Vec<Statement> {
+    let mut builder = Builder {
+        pieces,
+        significant: (0..pieces.len())
+            .filter(|&i| pieces[i].is_significant())
+            .collect(),
+        position: 0,
+    };
+    let mut statements = Vec::new();
+    while builder.position < builder.significant.len() {
+        statements.extend(builder.statements());
+        if let Some(&stray) = builder.significant.get(builder.position) {
+            statements.push(Statement {
+                pieces: stray..stray + 1,
+                blocks: Vec::new(),
+            });
+            builder.position += 1;
+        }
+    }
+    statements
+}
+
+struct Builder<'p, 'a> {
+    pieces: &'p [Piece<'a>],
+    /// Indexes of the pieces that are code.
+    significant: Vec<usize>,
+    position: usize,
+}
+
+impl<'p, 'a> Builder<'p, 'a> {
+    fn peek(&self) -> Option<&'a TokenType> {
+        self.significant
+            .get(self.position)
+            .and_then(|&i| self.pieces[i].token_type())
+    }
+
+    /// Statements up to the `}` closing the block they are in, or the end.
+    fn statements(&mut self) -> Vec<Statement> {
+        let mut statements = Vec::new();
+        while self.position < self.significant.len() && self.peek() != Some(&TokenType::RightBrace)
+        {
+            statements.push(self.statement());
+        }
+        statements
+    }
+
+    fn statement(&mut self) -> Statement {
+        let first = self.significant[self.position];
+        let mut last = first;
+        let mut blocks = Vec::new();
+        let mut parens = 0usize;
+        let is_for = self.pieces[first].token_type() == Some(&TokenType::For);
+        while let Some(&index) = self.significant.get(self.position) {
+            let token_type = self.pieces[index].token_type();
+            // The `}` of the block this statement is in.
+            if token_type == Some(&TokenType::RightBrace) {
+                break;
+            }
+            self.position += 1;
+            last = index;
+            let ends = match token_type {
+                Some(TokenType::LeftParen) => {
+                    parens += 1;
+                    false
+                }
+                Some(TokenType::RightParen) => {
+                    parens = parens.saturating_sub(1);
+                    false
+                }
+                Some(TokenType::LeftBrace) => {
+                    let statements = self.statements();
+                    let close = self.significant.get(self.position).copied();
+                    match close {
+                        Some(close) => {
+                            self.position += 1;
+                            last = close;
+                        }
+                        None => last = statements.last().map_or(last, |s| s.pieces.end - 1),
+                    }
+                    blocks.push(Block {
+                        open: index,
+                        close,
+                        statements,
+                    });
+                    parens == 0
+                }
+                // Only the clauses of a `for` are separated by semicolons inside parentheses, so
+                // a parenthesis that is never closed doesn't swallow the statements after it.
+                Some(TokenType::Semicolon) => parens == 0 || !is_for,
+                _ => false,
+            };
+            if ends && self.peek() != Some(&TokenType::Else) {
+                break;
+            }
+        }
+        Statement {
+            pieces: first..last + 1,
+            blocks,
+        }
+    }
+}
+
+/// Piece ranges of the innermost statements that have an error in them, which are left as they
+/// are.  An error past the last statement, such as a missing `;` at the end, is put in the last
+/// one.
+pub(super) fn invalid(
+    statements: &[Statement],
+    pieces: &[Piece],
+    errors: impl IntoIterator<Item = usize>,
+) -> Vec<Range<usize>> {
+    let mut invalid: Vec<Range<usize>> = Vec::new();
+    for offset in errors {
+        let statement = innermost(statements, pieces, offset)
+            .or_else(|| statements.last().filter(|s| s.bytes(pieces).end <= offset));
+        if let Some(statement) = statement {
+            invalid.push(statement.pieces.clone());
+        }
+    }
+    // Only the outermost of nested ranges matters.
+    invalid.sort_by_key(|r| (r.start, std::cmp::Reverse(r.end)));
+    let mut outermost: Vec<Range<usize>> = Vec::new();
+    for range in invalid {
+        if outermost.last().is_none_or(|last| range.start >= last.end) {
+            outermost.push(range);
+        }
+    }
+    outermost
+}
+
+fn innermost<'s>(
+    statements: &'s [Statement],
+    pieces: &[Piece],
+    offset: usize,
+) -> Option<&'s Statement> {
+    let statement = statements
+        .iter()
+        .find(|s| s.bytes(pieces).contains(&offset))?;
+    statement
+        .blocks
+        .iter()
+        .find_map(|b| innermost(&b.statements, pieces, offset))
+        .or(Some(statement))
+}
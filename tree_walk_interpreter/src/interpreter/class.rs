@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+use crate::error::RuntimeError;
+use crate::interpreter::function::LoxFunction;
+use crate::interpreter::value::LoxValue;
+use crate::interpreter::Interpreter;
+use crate::scanner::tokens::LoxToken;
+use crate::LoxResult;
+
+#[derive(Debug)]
+pub struct LoxClass {
+    name: String,
+    superclass: Option<Rc<LoxClass>>,
+    methods: HashMap<String, Rc<LoxFunction>>,
+}
+
+impl LoxClass {
+    pub fn new(
+        name: &str,
+        superclass: Option<Rc<LoxClass>>,
+        methods: HashMap<String, Rc<LoxFunction>>,
+    ) -> LoxClass {
+        LoxClass {
+            name: name.to_string(),
+            superclass,
+            methods,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Looks up a method on this class, falling back to the superclass chain.
+    pub fn find_method(&self, name: &str) -> Option<Rc<LoxFunction>> {
+        self.methods.get(name).cloned().or_else(|| {
+            self.superclass
+                .as_ref()
+                .and_then(|superclass| superclass.find_method(name))
+        })
+    }
+
+    /// Calling a class takes the arguments of its initializer, or none if it doesn't have one.
+    pub fn arity(&self) -> usize {
+        self.find_method("init")
+            .map(|init| init.arity())
+            .unwrap_or(0)
+    }
+
+    /// Creates a new instance and runs the initializer on it, if there is one.
+    pub fn call(
+        class: &Rc<LoxClass>,
+        interpreter: &mut Interpreter,
+        arguments: Vec<LoxValue>,
+    ) -> LoxResult<LoxValue> {
+        let instance = LoxValue::Instance(Rc::new(RefCell::new(LoxInstance::new(class))));
+        if let Some(initializer) = class.find_method("init") {
+            initializer
+                .bind(instance.clone())
+                .call(interpreter, arguments)?;
+        }
+        Ok(instance)
+    }
+}
+
+impl Display for LoxClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+pub struct LoxInstance {
+    class: Rc<LoxClass>,
+    fields: HashMap<String, LoxValue>,
+}
+
+impl LoxInstance {
+    pub fn new(class: &Rc<LoxClass>) -> LoxInstance {
+        LoxInstance {
+            class: Rc::clone(class),
+            fields: HashMap::new(),
+        }
+    }
+
+    /// Looks up a property, fields shadow methods.  Methods are bound to the instance so `this`
+    /// keeps working when the method is stored and called later.
+    pub fn get(instance: &Rc<RefCell<LoxInstance>>, name: &LoxToken) -> LoxResult<LoxValue> {
+        if let Some(value) = instance.borrow().fields.get(&name.lexeme) {
+            return Ok(value.clone());
+        }
+
+        let method = instance.borrow().class.find_method(&name.lexeme);
+        match method {
+            Some(method) => Ok(LoxValue::Function(Rc::new(
+                method.bind(LoxValue::Instance(Rc::clone(instance))),
+            ))),
+            None => {
+                Err(
+                    RuntimeError::UndefinedProperty(name.line, name.column, name.lexeme.clone())
+                        .into(),
+                )
+            }
+        }
+    }
+
+    pub fn set(&mut self, name: &LoxToken, value: LoxValue) {
+        self.fields.insert(name.lexeme.clone(), value);
+    }
+}
+
+// Fields can refer back to the instance itself, so only their names are shown.
+impl Debug for LoxInstance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoxInstance")
+            .field("class", &self.class.name())
+            .field("fields", &self.fields.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Display for LoxInstance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} instance", self.class.name())
+    }
+}
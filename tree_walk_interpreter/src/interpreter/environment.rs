@@ -0,0 +1,89 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::interpreter::value::LoxValue;
+
+pub type EnvironmentRef = Rc<RefCell<Environment>>;
+
+/// A single scope of variables, linked to the scope it is nested in.
+///
+/// Environments are shared, a closure holds on to the environment it was declared in for as long
+/// as the closure itself is alive.
+#[derive(Debug, Default)]
+pub struct Environment {
+    values: HashMap<String, LoxValue>,
+    enclosing: Option<EnvironmentRef>,
+}
+
+impl Environment {
+    pub fn new_global() -> EnvironmentRef {
+        Rc::new(RefCell::new(Environment::default()))
+    }
+
+    pub fn new_enclosed(enclosing: &EnvironmentRef) -> EnvironmentRef {
+        Rc::new(RefCell::new(Environment {
+            values: HashMap::new(),
+            enclosing: Some(Rc::clone(enclosing)),
+        }))
+    }
+
+    pub fn define(&mut self, name: &str, value: LoxValue) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    /// Looks up a variable in this scope only, globals are resolved this way.
+    pub fn get(&self, name: &str) -> Option<LoxValue> {
+        self.values.get(name).cloned()
+    }
+
+    /// Every variable in this scope, in no particular order.
+    pub fn variables(&self) -> impl Iterator<Item = (&str, &LoxValue)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Assigns an existing variable in this scope only, returning false if it was never defined.
+    pub fn assign(&mut self, name: &str, value: LoxValue) -> bool {
+        match self.values.get_mut(name) {
+            Some(slot) => {
+                *slot = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Looks up a variable `distance` scopes up the chain, as computed by the resolver.
+    pub fn get_at(environment: &EnvironmentRef, distance: usize, name: &str) -> Option<LoxValue> {
+        Environment::ancestor(environment, distance)
+            .borrow()
+            .get(name)
+    }
+
+    /// Assigns a variable `distance` scopes up the chain, as computed by the resolver.
+    pub fn assign_at(
+        environment: &EnvironmentRef,
+        distance: usize,
+        name: &str,
+        value: LoxValue,
+    ) -> bool {
+        Environment::ancestor(environment, distance)
+            .borrow_mut()
+            .assign(name, value)
+    }
+
+    fn ancestor(environment: &EnvironmentRef, distance: usize) -> EnvironmentRef {
+        let mut environment = Rc::clone(environment);
+        for _ in 0..distance {
+            let enclosing = environment
+                .borrow()
+                .enclosing
+                .clone()
+                .expect("resolver computed a distance deeper than the environment chain");
+            environment = enclosing;
+        }
+        environment
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+use crate::interpreter::environment::{Environment, EnvironmentRef};
+use crate::interpreter::value::LoxValue;
+use crate::interpreter::{Interpreter, Unwind};
+use crate::parser::ast::FunctionDeclaration;
+use crate::resolver::Locals;
+use crate::LoxResult;
+
+/// A user defined Lox function along with the environment it closes over.
+pub struct LoxFunction {
+    declaration: Rc<FunctionDeclaration>,
+    closure: EnvironmentRef,
+    /// Scope distances of the script the function was declared in, which its body was resolved
+    /// with.  They are dropped along with the last function that needs them.
+    locals: Rc<Locals>,
+    is_initializer: bool,
+}
+
+impl LoxFunction {
+    pub fn new(
+        declaration: Rc<FunctionDeclaration>,
+        closure: EnvironmentRef,
+        locals: Rc<Locals>,
+    ) -> LoxFunction {
+        LoxFunction {
+            declaration,
+            closure,
+            locals,
+            is_initializer: false,
+        }
+    }
+
+    /// A class' `init` method, which always returns the instance it initialized.
+    pub fn new_initializer(
+        declaration: Rc<FunctionDeclaration>,
+        closure: EnvironmentRef,
+        locals: Rc<Locals>,
+    ) -> LoxFunction {
+        LoxFunction {
+            declaration,
+            closure,
+            locals,
+            is_initializer: true,
+        }
+    }
+
+    /// Creates a copy of this method whose closure has `this` bound to the given instance.
+    pub fn bind(&self, instance: LoxValue) -> LoxFunction {
+        let environment = Environment::new_enclosed(&self.closure);
+        environment.borrow_mut().define("this", instance);
+        LoxFunction {
+            declaration: Rc::clone(&self.declaration),
+            closure: environment,
+            locals: Rc::clone(&self.locals),
+            is_initializer: self.is_initializer,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.declaration.name.lexeme
+    }
+
+    pub fn arity(&self) -> usize {
+        self.declaration.params.len()
+    }
+
+    pub fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: Vec<LoxValue>,
+    ) -> LoxResult<LoxValue> {
+        let environment = Environment::new_enclosed(&self.closure);
+        for (param, argument) in self.declaration.params.iter().zip(arguments) {
+            environment.borrow_mut().define(&param.lexeme, argument);
+        }
+
+        let caller_locals = std::mem::replace(&mut interpreter.locals, Rc::clone(&self.locals));
+        let result = interpreter.execute_block(&self.declaration.body, environment);
+        interpreter.locals = caller_locals;
+        let value = match result {
+            Ok(()) => LoxValue::Nil,
+            Err(Unwind::Return(value)) => value,
+            Err(Unwind::Error(e)) => return Err(e),
+        };
+
+        if self.is_initializer {
+            // The resolver only allows bare `return;` in initializers, which still yields `this`.
+            return Ok(Environment::get_at(&self.closure, 0, "this").unwrap_or(LoxValue::Nil));
+        }
+        Ok(value)
+    }
+}
+
+// The closure may (indirectly) contain this very function, so it is left out to avoid recursing
+// forever.
+impl Debug for LoxFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoxFunction")
+            .field("name", &self.name())
+            .field("arity", &self.arity())
+            .finish()
+    }
+}
+
+impl Display for LoxFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<fn {}>", self.name())
+    }
+}
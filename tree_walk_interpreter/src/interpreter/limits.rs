@@ -0,0 +1,113 @@
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+/// Resources a single run of a script (or a call into it from the host) may use before it is
+/// stopped with [LimitExceeded](crate::error::RuntimeError::LimitExceeded), so that untrusted
+/// scripts can't take down the host.  Every limit is off by default.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Limits {
+    /// Steps are loop iterations and function calls, the only ways a script can keep running for
+    /// longer than it takes to read it.
+    pub max_steps: Option<u64>,
+    /// Unlike the interpreter's own stack overflow check this is reported as a limit.
+    pub max_call_depth: Option<usize>,
+    /// Instances, closures, classes and strings created by the script, whether or not they are
+    /// still alive.
+    pub max_heap_objects: Option<usize>,
+    /// Length in bytes of any string built by concatenation.
+    pub max_string_len: Option<usize>,
+    pub wall_clock_timeout: Option<Duration>,
+}
+
+/// Which of the [Limits] was exceeded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Limit {
+    Steps,
+    CallDepth,
+    HeapObjects,
+    StringLength,
+    WallClockTimeout,
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Limit::Steps => "step",
+            Limit::CallDepth => "call depth",
+            Limit::HeapObjects => "heap object",
+            Limit::StringLength => "string length",
+            Limit::WallClockTimeout => "wall clock",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How much of its [Limits] the current run has used.
+#[derive(Debug, Default)]
+pub(crate) struct Budget {
+    limits: Limits,
+    steps: u64,
+    heap_objects: usize,
+    deadline: Option<Instant>,
+}
+
+impl Budget {
+    pub fn new(limits: Limits) -> Budget {
+        Budget {
+            limits,
+            ..Budget::default()
+        }
+    }
+
+    /// Starts a fresh run with the whole budget available.
+    pub fn start(&mut self) {
+        self.steps = 0;
+        self.heap_objects = 0;
+        self.deadline = self
+            .limits
+            .wall_clock_timeout
+            .map(|timeout| Instant::now() + timeout);
+    }
+
+    /// Records a loop iteration or call, which is also when the clock is checked.
+    pub fn step(&mut self) -> Result<(), Limit> {
+        self.steps += 1;
+        if self.limits.max_steps.is_some_and(|max| self.steps > max) {
+            return Err(Limit::Steps);
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(Limit::WallClockTimeout);
+        }
+        Ok(())
+    }
+
+    pub fn allocate(&mut self) -> Result<(), Limit> {
+        self.heap_objects += 1;
+        if self
+            .limits
+            .max_heap_objects
+            .is_some_and(|max| self.heap_objects > max)
+        {
+            return Err(Limit::HeapObjects);
+        }
+        Ok(())
+    }
+
+    /// Checks that a call can be made while `depth` calls are already in progress.
+    pub fn check_call_depth(&self, depth: usize) -> Result<(), Limit> {
+        if self.limits.max_call_depth.is_some_and(|max| depth >= max) {
+            return Err(Limit::CallDepth);
+        }
+        Ok(())
+    }
+
+    pub fn check_string_len(&self, len: usize) -> Result<(), Limit> {
+        if self.limits.max_string_len.is_some_and(|max| len > max) {
+            return Err(Limit::StringLength);
+        }
+        Ok(())
+    }
+}
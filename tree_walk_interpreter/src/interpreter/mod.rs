@@ -0,0 +1,635 @@
+use std::io::Write;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::{LoxError, LoxResult, RuntimeError, StackFrame, StackTrace};
+use crate::interpreter::class::{LoxClass, LoxInstance};
+use crate::interpreter::environment::{Environment, EnvironmentRef};
+use crate::interpreter::function::LoxFunction;
+use crate::interpreter::limits::{Budget, Limit, Limits};
+use crate::interpreter::native::NativeRegistry;
+use crate::interpreter::value::LoxValue;
+use crate::parser::ast::{Expr, ExprId, Stmt};
+use crate::resolver::Locals;
+use crate::scanner::tokens::{LoxToken, TokenType};
+
+pub mod class;
+pub mod environment;
+pub mod function;
+pub mod limits;
+pub mod native;
+pub mod value;
+
+#[cfg(test)]
+mod tests;
+
+/// Ways execution of a statement can stop early, unwinding up to whoever handles it.
+pub(crate) enum Unwind {
+    /// A `return` statement, unwinds to the enclosing function call.
+    Return(LoxValue),
+    /// A runtime error, unwinds all the way out of the interpreter.
+    Error(LoxError),
+}
+
+impl From<LoxError> for Unwind {
+    fn from(e: LoxError) -> Self {
+        Unwind::Error(e)
+    }
+}
+
+impl From<RuntimeError> for Unwind {
+    fn from(e: RuntimeError) -> Self {
+        Unwind::Error(e.into())
+    }
+}
+
+type ExecResult = Result<(), Unwind>;
+
+/// How many Lox calls may be nested before the program is stopped with a stack overflow.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 10_000;
+
+/// When less than this much native stack is left a call switches to a freshly allocated segment.
+const STACK_RED_ZONE: usize = 128 * 1024;
+/// Size of each freshly allocated stack segment.
+const STACK_SEGMENT_SIZE: usize = 4 * 1024 * 1024;
+
+/// A Lox function call that is currently executing.
+struct CallFrame {
+    function: String,
+    /// Line of the call expression in the caller.
+    call_line: usize,
+}
+
+/// Tree walking interpreter that executes [resolved](crate::resolver::resolve) statements.
+pub struct Interpreter {
+    globals: EnvironmentRef,
+    environment: EnvironmentRef,
+    /// Scope distances of the script running now, or of the function being called.
+    locals: Rc<Locals>,
+    call_stack: Vec<CallFrame>,
+    max_call_depth: usize,
+    /// Where `print` statements write to.
+    stdout: Box<dyn Write>,
+    budget: Budget,
+    /// Set from another thread (or a signal handler) to stop the running script.
+    interrupt: Arc<AtomicBool>,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Interpreter {
+        Interpreter::with_max_call_depth(DEFAULT_MAX_CALL_DEPTH)
+    }
+
+    /// Creates an interpreter that raises a stack overflow error once more than `max_call_depth`
+    /// Lox calls are nested.
+    pub fn with_max_call_depth(max_call_depth: usize) -> Interpreter {
+        let globals = Environment::new_global();
+        let mut interpreter = Interpreter {
+            environment: Rc::clone(&globals),
+            globals,
+            locals: Rc::default(),
+            call_stack: Vec::new(),
+            max_call_depth,
+            stdout: Box::new(std::io::stdout()),
+            budget: Budget::default(),
+            interrupt: Arc::new(AtomicBool::new(false)),
+        };
+        interpreter.define_natives(&NativeRegistry::standard());
+        interpreter
+    }
+
+    /// Sends the output of `print` statements to `stdout` instead of the process' standard output.
+    pub fn set_stdout(&mut self, stdout: impl Write + 'static) {
+        self.stdout = Box::new(stdout);
+    }
+
+    /// Stops every run that exceeds one of the limits, none are enforced unless this is called.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.budget = Budget::new(limits);
+    }
+
+    /// A flag that stops the running script with an [Interrupted](RuntimeError::Interrupted) error
+    /// once set.  It is checked whenever a loop goes around or a function is called, and cleared
+    /// when the next script starts, so an interrupt that arrives between scripts is dropped.
+    pub fn interrupt_handle(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.interrupt)
+    }
+
+    /// Uses an existing flag, so that one handle can interrupt several interpreters.
+    pub fn set_interrupt_handle(&mut self, interrupt: Arc<AtomicBool>) {
+        self.interrupt = interrupt;
+    }
+
+    /// Forgets every global and starts over with just the standard native functions, the output,
+    /// limits and interrupt handle are kept.
+    pub fn reset(&mut self) {
+        self.globals = Environment::new_global();
+        self.environment = Rc::clone(&self.globals);
+        self.locals = Rc::default();
+        self.call_stack.clear();
+        self.define_natives(&NativeRegistry::standard());
+    }
+
+    /// Defines every function in the registry as a global, replacing any global of the same name.
+    pub fn define_natives(&mut self, registry: &NativeRegistry) {
+        for function in registry.functions() {
+            self.globals.borrow_mut().define(
+                function.name(),
+                LoxValue::NativeFunction(Rc::clone(function)),
+            );
+        }
+    }
+
+    /// Execute the statements, using the scope distances the resolver computed for them.  Only the
+    /// functions declared by the statements keep the distances once a later script runs.
+    pub fn interpret(&mut self, statements: &[Stmt], locals: Locals) -> LoxResult<()> {
+        self.locals = Rc::new(locals);
+        self.start_run();
+        for statement in statements {
+            match self.execute(statement) {
+                Ok(()) => {}
+                Err(Unwind::Error(e)) => return Err(self.with_stack_trace(e)),
+                // The resolver rejects top level returns.
+                Err(Unwind::Return(_)) => unreachable!("return outside of a function"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluate a lone expression, using the scope distances the resolver computed for it.
+    pub fn interpret_expression(&mut self, expr: &Expr, locals: Locals) -> LoxResult<LoxValue> {
+        self.locals = Rc::new(locals);
+        self.start_run();
+        self.evaluate(expr).map_err(|e| self.with_stack_trace(e))
+    }
+
+    /// Defines (or redefines) a global variable visible to every script run afterwards.
+    pub fn define_global(&mut self, name: &str, value: LoxValue) {
+        self.globals.borrow_mut().define(name, value);
+    }
+
+    /// Every global variable sorted by name, including the native functions.
+    pub fn globals(&self) -> Vec<(String, LoxValue)> {
+        let mut globals: Vec<(String, LoxValue)> = self
+            .globals
+            .borrow()
+            .variables()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect();
+        globals.sort_by(|(a, _), (b, _)| a.cmp(b));
+        globals
+    }
+
+    /// Looks up a global variable, mostly useful to inspect the result of a script.
+    pub fn global(&self, name: &str) -> Option<LoxValue> {
+        self.globals.borrow().get(name)
+    }
+
+    fn execute(&mut self, statement: &Stmt) -> ExecResult {
+        match statement {
+            Stmt::Block(statements) => {
+                let environment = Environment::new_enclosed(&self.environment);
+                self.execute_block(statements, environment)
+            }
+            Stmt::Class(declaration) => {
+                self.budget
+                    .allocate()
+                    .map_err(limit_exceeded(&declaration.name))?;
+                let superclass = match &declaration.superclass {
+                    Some(superclass) => match self.evaluate(superclass)? {
+                        LoxValue::Class(class) => Some(class),
+                        _ => {
+                            let name = match superclass {
+                                Expr::Variable { name, .. } => name,
+                                _ => &declaration.name,
+                            };
+                            return Err(RuntimeError::SuperclassMustBeAClass(
+                                name.line,
+                                name.column,
+                            )
+                            .into());
+                        }
+                    },
+                    None => None,
+                };
+
+                // Methods of a subclass close over an environment holding `super`.
+                let enclosing = Rc::clone(&self.environment);
+                if let Some(superclass) = &superclass {
+                    self.environment = Environment::new_enclosed(&self.environment);
+                    self.environment
+                        .borrow_mut()
+                        .define("super", LoxValue::Class(Rc::clone(superclass)));
+                }
+
+                let methods = declaration
+                    .methods
+                    .iter()
+                    .map(|method| {
+                        let closure = Rc::clone(&self.environment);
+                        let locals = Rc::clone(&self.locals);
+                        let function = if method.name.lexeme == "init" {
+                            LoxFunction::new_initializer(Rc::clone(method), closure, locals)
+                        } else {
+                            LoxFunction::new(Rc::clone(method), closure, locals)
+                        };
+                        (method.name.lexeme.clone(), Rc::new(function))
+                    })
+                    .collect();
+                let class = LoxClass::new(&declaration.name.lexeme, superclass, methods);
+
+                self.environment = enclosing;
+                self.environment
+                    .borrow_mut()
+                    .define(&declaration.name.lexeme, LoxValue::Class(Rc::new(class)));
+                Ok(())
+            }
+            Stmt::Expression(expr) => {
+                self.evaluate(expr)?;
+                Ok(())
+            }
+            Stmt::Function(declaration) => {
+                self.budget
+                    .allocate()
+                    .map_err(limit_exceeded(&declaration.name))?;
+                let function = LoxFunction::new(
+                    Rc::clone(declaration),
+                    Rc::clone(&self.environment),
+                    Rc::clone(&self.locals),
+                );
+                self.environment.borrow_mut().define(
+                    &declaration.name.lexeme,
+                    LoxValue::Function(Rc::new(function)),
+                );
+                Ok(())
+            }
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                if self.evaluate(condition)?.is_truthy() {
+                    self.execute(then_branch)
+                } else if let Some(else_branch) = else_branch {
+                    self.execute(else_branch)
+                } else {
+                    Ok(())
+                }
+            }
+            Stmt::Print(expr) => {
+                let value = self.evaluate(expr)?;
+                // Like `println!` would, but a reader that went away (such as `head`) isn't worth
+                // stopping the script over.
+                let _ = writeln!(self.stdout, "{}", value);
+                Ok(())
+            }
+            Stmt::Return { value, .. } => {
+                let value = match value {
+                    Some(value) => self.evaluate(value)?,
+                    None => LoxValue::Nil,
+                };
+                Err(Unwind::Return(value))
+            }
+            Stmt::Var { name, initializer } => {
+                let value = match initializer {
+                    Some(initializer) => self.evaluate(initializer)?,
+                    None => LoxValue::Nil,
+                };
+                self.environment.borrow_mut().define(&name.lexeme, value);
+                Ok(())
+            }
+            Stmt::While {
+                keyword,
+                condition,
+                body,
+            } => {
+                while self.evaluate(condition)?.is_truthy() {
+                    self.check_interrupt(keyword)?;
+                    self.budget.step().map_err(limit_exceeded(keyword))?;
+                    self.execute(body)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Executes the statements in the given environment, restoring the current one afterwards
+    /// no matter how the block was exited.
+    pub(crate) fn execute_block(
+        &mut self,
+        statements: &[Stmt],
+        environment: EnvironmentRef,
+    ) -> ExecResult {
+        let previous = std::mem::replace(&mut self.environment, environment);
+        let result = statements.iter().try_for_each(|s| self.execute(s));
+        self.environment = previous;
+        result
+    }
+
+    fn evaluate(&mut self, expr: &Expr) -> LoxResult<LoxValue> {
+        match expr {
+            Expr::Assign { id, name, value } => {
+                let value = self.evaluate(value)?;
+                let assigned = match self.locals.get(id) {
+                    Some(&distance) => Environment::assign_at(
+                        &self.environment,
+                        distance,
+                        &name.lexeme,
+                        value.clone(),
+                    ),
+                    None => self
+                        .globals
+                        .borrow_mut()
+                        .assign(&name.lexeme, value.clone()),
+                };
+                if !assigned {
+                    return Err(undefined_variable(name));
+                }
+                Ok(value)
+            }
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                let value = binary(operator, left, right)?;
+                if let LoxValue::String(s) = &value {
+                    self.budget.allocate().map_err(limit_exceeded(operator))?;
+                    self.budget
+                        .check_string_len(s.len())
+                        .map_err(limit_exceeded(operator))?;
+                }
+                Ok(value)
+            }
+            Expr::Call {
+                callee,
+                paren,
+                arguments,
+            } => {
+                let callee = self.evaluate(callee)?;
+                let arguments = arguments
+                    .iter()
+                    .map(|a| self.evaluate(a))
+                    .collect::<LoxResult<Vec<LoxValue>>>()?;
+                self.call(callee, paren, arguments)
+            }
+            Expr::Get { object, name } => match self.evaluate(object)? {
+                LoxValue::Instance(instance) => LoxInstance::get(&instance, name),
+                _ => Err(RuntimeError::OnlyInstancesHaveProperties(name.line, name.column).into()),
+            },
+            Expr::Grouping(expr) => self.evaluate(expr),
+            Expr::Literal(literal) => Ok(literal.into()),
+            Expr::Logical {
+                left,
+                operator,
+                right,
+            } => {
+                let left = self.evaluate(left)?;
+                let short_circuits = match operator.token_type {
+                    TokenType::Or => left.is_truthy(),
+                    _ => !left.is_truthy(),
+                };
+                if short_circuits {
+                    Ok(left)
+                } else {
+                    self.evaluate(right)
+                }
+            }
+            Expr::Set {
+                object,
+                name,
+                value,
+            } => {
+                let instance = match self.evaluate(object)? {
+                    LoxValue::Instance(instance) => instance,
+                    _ => {
+                        return Err(
+                            RuntimeError::OnlyInstancesHaveFields(name.line, name.column).into(),
+                        )
+                    }
+                };
+                let value = self.evaluate(value)?;
+                instance.borrow_mut().set(name, value.clone());
+                Ok(value)
+            }
+            Expr::Super {
+                id,
+                keyword,
+                method,
+            } => self.look_up_super_method(*id, keyword, method),
+            Expr::This { id, keyword } => self.look_up_variable(*id, keyword),
+            Expr::Unary { operator, right } => {
+                let right = self.evaluate(right)?;
+                match operator.token_type {
+                    TokenType::Bang => Ok(LoxValue::Bool(!right.is_truthy())),
+                    _ => match right {
+                        LoxValue::Number(n) => Ok(LoxValue::Number(-n)),
+                        _ => Err(
+                            RuntimeError::OperandMustBeNumber(operator.line, operator.column)
+                                .into(),
+                        ),
+                    },
+                }
+            }
+            Expr::Variable { id, name } => self.look_up_variable(*id, name),
+        }
+    }
+
+    /// Calls a Lox function, native function or class from the host.  There is no call
+    /// expression to blame errors on, so they are reported at line 0.
+    pub fn call_value(
+        &mut self,
+        callee: LoxValue,
+        arguments: Vec<LoxValue>,
+    ) -> LoxResult<LoxValue> {
+        let paren = LoxToken {
+            token_type: TokenType::RightParen,
+            lexeme: ")".to_string(),
+            line: 0,
+            column: 0,
+        };
+        self.start_run();
+        self.call(callee, &paren, arguments)
+            .map_err(|e| self.with_stack_trace(e))
+    }
+
+    fn call(
+        &mut self,
+        callee: LoxValue,
+        paren: &LoxToken,
+        arguments: Vec<LoxValue>,
+    ) -> LoxResult<LoxValue> {
+        let (name, arity) = match &callee {
+            LoxValue::Function(function) => (function.name().to_string(), function.arity()),
+            LoxValue::NativeFunction(function) => (function.name().to_string(), function.arity()),
+            LoxValue::Class(class) => (class.name().to_string(), class.arity()),
+            _ => return Err(RuntimeError::NotCallable(paren.line, paren.column).into()),
+        };
+        if arguments.len() != arity {
+            return Err(RuntimeError::ArityMismatch(
+                paren.line,
+                paren.column,
+                arity,
+                arguments.len(),
+            )
+            .into());
+        }
+
+        self.check_interrupt(paren)?;
+        self.budget
+            .check_call_depth(self.call_stack.len())
+            .and_then(|()| self.budget.step())
+            .map_err(limit_exceeded(paren))?;
+        if let LoxValue::Class(_) = callee {
+            self.budget.allocate().map_err(limit_exceeded(paren))?;
+        }
+        if self.call_stack.len() >= self.max_call_depth {
+            return Err(RuntimeError::StackOverflow(paren.line, paren.column).into());
+        }
+
+        self.call_stack.push(CallFrame {
+            function: name,
+            call_line: paren.line,
+        });
+        // Every Lox call recurses through the interpreter on the native stack, so grow it on
+        // demand rather than letting deep (but legal) recursion crash the process.
+        let result = stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || match callee {
+            LoxValue::Function(function) => function.call(self, arguments),
+            LoxValue::NativeFunction(function) => function.call(&arguments).map_err(|e| {
+                RuntimeError::NativeFunction(
+                    paren.line,
+                    paren.column,
+                    function.name().to_string(),
+                    e,
+                )
+                .into()
+            }),
+            LoxValue::Class(class) => LoxClass::call(&class, self, arguments),
+            _ => unreachable!("checked callable above"),
+        })
+        .map_err(|e| self.with_stack_trace(e));
+        self.call_stack.pop();
+
+        result
+    }
+
+    fn start_run(&mut self) {
+        self.budget.start();
+        self.interrupt.store(false, Ordering::Relaxed);
+    }
+
+    /// Stops the script if it was interrupted.
+    fn check_interrupt(&self, token: &LoxToken) -> LoxResult<()> {
+        if self.interrupt.load(Ordering::Relaxed) {
+            return Err(RuntimeError::Interrupted(token.line, token.column).into());
+        }
+        Ok(())
+    }
+
+    /// Records the current call stack on a runtime error that doesn't have one yet, which is
+    /// done as the error unwinds out of the innermost call so the stack is still intact.
+    fn with_stack_trace(&self, e: LoxError) -> LoxError {
+        match e {
+            LoxError::RuntimeError(error, StackTrace(frames)) if frames.is_empty() => {
+                let mut frames = Vec::with_capacity(self.call_stack.len() + 1);
+                let mut line = error.span().0;
+                for call in self.call_stack.iter().rev() {
+                    frames.push(StackFrame {
+                        function: Some(call.function.clone()),
+                        line,
+                    });
+                    line = call.call_line;
+                }
+                frames.push(StackFrame {
+                    function: None,
+                    line,
+                });
+                LoxError::RuntimeError(error, StackTrace(frames))
+            }
+            e => e,
+        }
+    }
+
+    /// `super.method` binds the superclass' method to the current `this`, which always lives in
+    /// the environment just inside the one holding `super`.
+    fn look_up_super_method(
+        &self,
+        id: ExprId,
+        keyword: &LoxToken,
+        method: &LoxToken,
+    ) -> LoxResult<LoxValue> {
+        let distance = self.locals.get(&id).copied().unwrap_or(0);
+        let superclass = match Environment::get_at(&self.environment, distance, "super") {
+            Some(LoxValue::Class(superclass)) => superclass,
+            _ => return Err(undefined_variable(keyword)),
+        };
+        let instance = Environment::get_at(&self.environment, distance.saturating_sub(1), "this")
+            .unwrap_or(LoxValue::Nil);
+
+        match superclass.find_method(&method.lexeme) {
+            Some(m) => Ok(LoxValue::Function(Rc::new(m.bind(instance)))),
+            None => Err(RuntimeError::UndefinedProperty(
+                method.line,
+                method.column,
+                method.lexeme.clone(),
+            )
+            .into()),
+        }
+    }
+
+    fn look_up_variable(&self, id: ExprId, name: &LoxToken) -> LoxResult<LoxValue> {
+        let value = match self.locals.get(&id) {
+            Some(&distance) => Environment::get_at(&self.environment, distance, &name.lexeme),
+            None => self.globals.borrow().get(&name.lexeme),
+        };
+        value.ok_or_else(|| undefined_variable(name))
+    }
+}
+
+fn limit_exceeded(token: &LoxToken) -> impl FnOnce(Limit) -> LoxError + '_ {
+    move |limit| RuntimeError::LimitExceeded(token.line, token.column, limit).into()
+}
+
+fn undefined_variable(name: &LoxToken) -> LoxError {
+    RuntimeError::UndefinedVariable(name.line, name.column, name.lexeme.clone()).into()
+}
+
+fn binary(operator: &LoxToken, left: LoxValue, right: LoxValue) -> LoxResult<LoxValue> {
+    use LoxValue::{Bool, Number};
+
+    let value = match (&operator.token_type, left, right) {
+        (TokenType::EqualEqual, l, r) => Bool(l == r),
+        (TokenType::BangEqual, l, r) => Bool(l != r),
+        (TokenType::Plus, Number(l), Number(r)) => Number(l + r),
+        (TokenType::Plus, LoxValue::String(l), LoxValue::String(r)) => {
+            LoxValue::String(format!("{}{}", l, r).into())
+        }
+        (TokenType::Plus, _, _) => {
+            return Err(RuntimeError::OperandsMustBeNumbersOrStrings(
+                operator.line,
+                operator.column,
+            )
+            .into())
+        }
+        (TokenType::Minus, Number(l), Number(r)) => Number(l - r),
+        (TokenType::Star, Number(l), Number(r)) => Number(l * r),
+        (TokenType::Slash, Number(l), Number(r)) => Number(l / r),
+        (TokenType::Greater, Number(l), Number(r)) => Bool(l > r),
+        (TokenType::GreaterEqual, Number(l), Number(r)) => Bool(l >= r),
+        (TokenType::Less, Number(l), Number(r)) => Bool(l < r),
+        (TokenType::LessEqual, Number(l), Number(r)) => Bool(l <= r),
+        _ => {
+            return Err(RuntimeError::OperandsMustBeNumbers(operator.line, operator.column).into())
+        }
+    };
+    Ok(value)
+}
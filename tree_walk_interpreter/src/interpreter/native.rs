@@ -0,0 +1,234 @@
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::NativeError;
+use crate::interpreter::value::LoxValue;
+
+/// A function implemented by the host in Rust and callable from Lox code.
+///
+/// Most host functions don't need to implement this directly, any closure taking and returning
+/// types convertible to and from [LoxValue] can be registered with
+/// [NativeRegistry::register_fn].
+pub trait NativeFunction {
+    fn name(&self) -> &str;
+
+    /// Number of arguments, checked by the interpreter before [call](NativeFunction::call).
+    fn arity(&self) -> usize;
+
+    fn call(&self, arguments: &[LoxValue]) -> Result<LoxValue, NativeError>;
+}
+
+impl Debug for dyn NativeFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NativeFunction")
+            .field("name", &self.name())
+            .field("arity", &self.arity())
+            .finish()
+    }
+}
+
+/// Host functions that get defined as globals when an interpreter is created.
+#[derive(Clone, Debug, Default)]
+pub struct NativeRegistry {
+    functions: Vec<Rc<dyn NativeFunction>>,
+}
+
+impl NativeRegistry {
+    /// An empty registry, without even the standard library.
+    pub fn new() -> NativeRegistry {
+        NativeRegistry::default()
+    }
+
+    /// The functions every Lox program can rely on, currently just `clock()`.
+    pub fn standard() -> NativeRegistry {
+        let mut registry = NativeRegistry::new();
+        registry.register_fn("clock", clock);
+        registry
+    }
+
+    pub fn register(&mut self, function: impl NativeFunction + 'static) -> &mut NativeRegistry {
+        self.functions.push(Rc::new(function));
+        self
+    }
+
+    /// Registers a closure, its arity and argument conversions are derived from its signature.
+    pub fn register_fn<Args, F>(&mut self, name: &str, function: F) -> &mut NativeRegistry
+    where
+        F: IntoNativeFunction<Args>,
+    {
+        self.functions.push(function.into_native_function(name));
+        self
+    }
+
+    pub fn functions(&self) -> impl Iterator<Item = &Rc<dyn NativeFunction>> {
+        self.functions.iter()
+    }
+}
+
+/// Seconds since the unix epoch, used by the benchmarks to time themselves.
+fn clock() -> Result<f64, NativeError> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .map_err(|_| NativeError::Failed("The system clock is set before 1970.".to_string()))
+}
+
+/// Conversion of a Lox argument to the Rust type a native function expects.
+pub trait FromLoxValue: Sized {
+    /// What the argument should have been, used in error messages.
+    const EXPECTED: &'static str;
+
+    fn from_lox_value(value: &LoxValue) -> Option<Self>;
+}
+
+impl FromLoxValue for LoxValue {
+    const EXPECTED: &'static str = "a value";
+
+    fn from_lox_value(value: &LoxValue) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+impl FromLoxValue for f64 {
+    const EXPECTED: &'static str = "a number";
+
+    fn from_lox_value(value: &LoxValue) -> Option<Self> {
+        match value {
+            LoxValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+impl FromLoxValue for bool {
+    const EXPECTED: &'static str = "a boolean";
+
+    fn from_lox_value(value: &LoxValue) -> Option<Self> {
+        match value {
+            LoxValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+impl FromLoxValue for String {
+    const EXPECTED: &'static str = "a string";
+
+    fn from_lox_value(value: &LoxValue) -> Option<Self> {
+        match value {
+            LoxValue::String(s) => Some(s.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Conversion of a native function's return value back into Lox.
+pub trait IntoNativeResult {
+    fn into_native_result(self) -> Result<LoxValue, NativeError>;
+}
+
+impl<T: Into<LoxValue>> IntoNativeResult for T {
+    fn into_native_result(self) -> Result<LoxValue, NativeError> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<LoxValue>> IntoNativeResult for Result<T, NativeError> {
+    fn into_native_result(self) -> Result<LoxValue, NativeError> {
+        self.map(Into::into)
+    }
+}
+
+impl From<f64> for LoxValue {
+    fn from(n: f64) -> Self {
+        LoxValue::Number(n)
+    }
+}
+
+impl From<bool> for LoxValue {
+    fn from(b: bool) -> Self {
+        LoxValue::Bool(b)
+    }
+}
+
+impl From<String> for LoxValue {
+    fn from(s: String) -> Self {
+        LoxValue::String(s.into())
+    }
+}
+
+impl From<&str> for LoxValue {
+    fn from(s: &str) -> Self {
+        LoxValue::String(s.into())
+    }
+}
+
+impl From<()> for LoxValue {
+    fn from(_: ()) -> Self {
+        LoxValue::Nil
+    }
+}
+
+/// Closures that can be turned into a [NativeFunction], `Args` is the tuple of their argument
+/// types and only exists to tell the implementations for different arities apart.
+pub trait IntoNativeFunction<Args> {
+    fn into_native_function(self, name: &str) -> Rc<dyn NativeFunction>;
+}
+
+struct ClosureFunction<F> {
+    name: String,
+    arity: usize,
+    function: F,
+}
+
+impl<F> NativeFunction for ClosureFunction<F>
+where
+    F: Fn(&[LoxValue]) -> Result<LoxValue, NativeError>,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn arity(&self) -> usize {
+        self.arity
+    }
+
+    fn call(&self, arguments: &[LoxValue]) -> Result<LoxValue, NativeError> {
+        (self.function)(arguments)
+    }
+}
+
+fn argument<T: FromLoxValue>(arguments: &[LoxValue], index: usize) -> Result<T, NativeError> {
+    T::from_lox_value(&arguments[index]).ok_or_else(|| {
+        NativeError::ArgumentType(index + 1, T::EXPECTED, arguments[index].to_string())
+    })
+}
+
+macro_rules! impl_into_native_function {
+    ($arity:expr $(, $arg:ident => $index:expr)*) => {
+        impl<Func, Ret, $($arg,)*> IntoNativeFunction<($($arg,)*)> for Func
+        where
+            Func: Fn($($arg),*) -> Ret + 'static,
+            Ret: IntoNativeResult,
+            $($arg: FromLoxValue,)*
+        {
+            #[allow(unused_variables)]
+            fn into_native_function(self, name: &str) -> Rc<dyn NativeFunction> {
+                Rc::new(ClosureFunction {
+                    name: name.to_string(),
+                    arity: $arity,
+                    function: move |arguments: &[LoxValue]| {
+                        self($(argument::<$arg>(arguments, $index)?),*).into_native_result()
+                    },
+                })
+            }
+        }
+    };
+}
+
+impl_into_native_function!(0);
+impl_into_native_function!(1, A => 0);
+impl_into_native_function!(2, A => 0, B => 1);
+impl_into_native_function!(3, A => 0, B => 1, C => 2);
+impl_into_native_function!(4, A => 0, B => 1, C => 2, D => 3);
@@ -0,0 +1,112 @@
+use crate::error::RuntimeError;
+use crate::interpreter::tests::{assert_global, global_string, interpret, runtime_error};
+use crate::interpreter::value::LoxValue;
+
+#[test]
+fn instance_fields() {
+    assert_global(
+        r#"
+class Point {}
+var p = Point();
+p.x = 1;
+p.y = 2;
+var result = p.x + p.y;
+"#,
+        "result",
+        LoxValue::Number(3.0),
+    );
+}
+
+#[test]
+fn initializer_and_methods() {
+    assert_global(
+        r#"
+class Counter {
+  init(start) {
+    this.count = start;
+  }
+
+  increment() {
+    this.count = this.count + 1;
+    return this;
+  }
+}
+var result = Counter(10).increment().increment().count;
+"#,
+        "result",
+        LoxValue::Number(12.0),
+    );
+}
+
+#[test]
+fn bound_method_keeps_this() {
+    assert_global(
+        r#"
+class Foo {
+  init() { this.name = "foo"; }
+  getName() { return this.name; }
+}
+var method = Foo().getName;
+var result = method();
+"#,
+        "result",
+        LoxValue::String("foo".into()),
+    );
+}
+
+#[test]
+fn init_early_return_returns_this() {
+    let interpreter = interpret(
+        r#"
+class Foo {
+  init() {
+    return;
+  }
+}
+var foo = Foo();
+var result = foo.init();
+"#,
+    )
+    .unwrap();
+    assert_eq!(global_string(&interpreter, "result"), "Foo instance");
+    assert_eq!(interpreter.global("result"), interpreter.global("foo"));
+}
+
+#[test]
+fn printing_classes_and_instances() {
+    let interpreter = interpret("class Foo {} var c = Foo; var i = Foo();").unwrap();
+    assert_eq!(global_string(&interpreter, "c"), "Foo");
+    assert_eq!(global_string(&interpreter, "i"), "Foo instance");
+}
+
+#[test]
+fn only_instances_have_properties() {
+    assert_eq!(
+        runtime_error("class Foo {} Foo.bar;"),
+        Some(RuntimeError::OnlyInstancesHaveProperties(1, 18))
+    );
+}
+
+#[test]
+fn only_instances_have_fields() {
+    assert_eq!(
+        runtime_error("var a = 1; a.b = 2;"),
+        Some(RuntimeError::OnlyInstancesHaveFields(1, 14))
+    );
+}
+
+#[test]
+fn undefined_property() {
+    assert_eq!(
+        runtime_error("class Foo {} Foo().bar;"),
+        Some(RuntimeError::UndefinedProperty(1, 20, "bar".to_string()))
+    );
+}
+
+#[test]
+fn class_arity_comes_from_init() {
+    assert_eq!(
+        runtime_error("class Foo { init(a, b) {} } Foo(1);"),
+        Some(RuntimeError::ArityMismatch(1, 34, 2, 1))
+    );
+}
@@ -0,0 +1,166 @@
+use std::rc::Rc;
+
+use crate::interpreter::tests::{assert_global, global_string, interpret, interpret_in};
+use crate::interpreter::value::LoxValue;
+use crate::interpreter::Interpreter;
+
+#[test]
+fn counter() {
+    assert_global(
+        r#"
+fun makeCounter() {
+  var i = 0;
+  fun count() {
+    i = i + 1;
+    return i;
+  }
+  return count;
+}
+
+var counter = makeCounter();
+counter();
+counter();
+var result = counter();
+"#,
+        "result",
+        LoxValue::Number(3.0),
+    );
+}
+
+#[test]
+fn assign_to_shadowed_later() {
+    let interpreter = interpret(
+        r#"
+var a = "global";
+var inner;
+{
+  fun assign() {
+    a = "assigned";
+  }
+
+  var a = "inner";
+  assign();
+  inner = a;
+}
+"#,
+    )
+    .unwrap();
+    assert_eq!(global_string(&interpreter, "inner"), "inner");
+    assert_eq!(global_string(&interpreter, "a"), "assigned");
+}
+
+#[test]
+fn reuse_closure_slot() {
+    assert_global(
+        r#"
+var result;
+{
+  var f;
+
+  {
+    var a = "a";
+    fun f_() { result = a; }
+    f = f_;
+  }
+
+  {
+    var b = "b";
+    f();
+  }
+}
+"#,
+        "result",
+        LoxValue::String("a".into()),
+    );
+}
+
+#[test]
+fn nested_closure() {
+    assert_global(
+        r#"
+var f;
+var result = "";
+
+fun f1() {
+  var a = "a";
+  fun f2() {
+    var b = "b";
+    fun f3() {
+      var c = "c";
+      fun f4() {
+        result = result + a + b + c;
+      }
+      f = f4;
+    }
+    f3();
+  }
+  f2();
+}
+f1();
+
+f();
+"#,
+        "result",
+        LoxValue::String("abc".into()),
+    );
+}
+
+#[test]
+fn closures_capture_variables_not_values() {
+    assert_global(
+        r#"
+var get;
+var set;
+{
+  var a = "initial";
+  fun g() { return a; }
+  fun s() { a = "updated"; }
+  get = g;
+  set = s;
+}
+set();
+var result = get();
+"#,
+        "result",
+        LoxValue::String("updated".into()),
+    );
+}
+
+#[test]
+fn closure_resolves_to_declaring_scope() {
+    assert_global(
+        r#"
+var a = "global";
+var first;
+var second;
+{
+  fun showA() {
+    return a;
+  }
+
+  first = showA();
+  var a = "block";
+  second = showA();
+}
+var result = first + second;
+"#,
+        "result",
+        LoxValue::String("globalglobal".into()),
+    );
+}
+
+#[test]
+fn scope_distances_are_dropped_with_the_last_closure() {
+    let mut interpreter = Interpreter::new();
+    interpret_in(&mut interpreter, "fun get() { var x = 1; return x; }").unwrap();
+    let declared = Rc::downgrade(&interpreter.locals);
+    for _ in 0..3 {
+        interpret_in(&mut interpreter, "var z = 0; { var y = 2; z = y + get(); }").unwrap();
+    }
+    assert_eq!(interpreter.global("z"), Some(LoxValue::Number(3.0)));
+    assert_eq!(interpreter.locals.len(), 1);
+    assert!(declared.upgrade().is_some());
+
+    interpret_in(&mut interpreter, "get = nil;").unwrap();
+    assert!(declared.upgrade().is_none());
+}
@@ -0,0 +1,61 @@
+use crate::error::RuntimeError;
+use crate::interpreter::tests::{assert_global, runtime_error};
+use crate::interpreter::value::LoxValue;
+
+#[test]
+fn arithmetic_precedence() {
+    assert_global(
+        "var result = 2 + 3 * 4 - 6 / 2;",
+        "result",
+        LoxValue::Number(11.0),
+    );
+}
+
+#[test]
+fn string_concatenation() {
+    assert_global(
+        r#"var result = "a" + "b";"#,
+        "result",
+        LoxValue::String("ab".into()),
+    );
+}
+
+#[test]
+fn recursion() {
+    assert_global(
+        r#"
+fun fib(n) {
+  if (n < 2) return n;
+  return fib(n - 1) + fib(n - 2);
+}
+var result = fib(10);
+"#,
+        "result",
+        LoxValue::Number(55.0),
+    );
+}
+
+#[test]
+fn for_loop() {
+    assert_global(
+        "var result = 0; for (var i = 0; i < 5; i = i + 1) result = result + i;",
+        "result",
+        LoxValue::Number(10.0),
+    );
+}
+
+#[test]
+fn undefined_variable() {
+    assert_eq!(
+        runtime_error("print nope;"),
+        Some(RuntimeError::UndefinedVariable(1, 7, "nope".to_string()))
+    );
+}
+
+#[test]
+fn arity_mismatch() {
+    assert_eq!(
+        runtime_error("fun f(a, b) {} f(1);"),
+        Some(RuntimeError::ArityMismatch(1, 19, 2, 1))
+    );
+}
@@ -0,0 +1,97 @@
+use crate::error::RuntimeError;
+use crate::interpreter::tests::{assert_global, runtime_error};
+use crate::interpreter::value::LoxValue;
+
+#[test]
+fn inherited_methods() {
+    assert_global(
+        r#"
+class A {
+  name() { return "A"; }
+}
+class B < A {}
+var result = B().name();
+"#,
+        "result",
+        LoxValue::String("A".into()),
+    );
+}
+
+#[test]
+fn overridden_method_calls_super() {
+    assert_global(
+        r#"
+class A {
+  describe() { return "A"; }
+}
+class B < A {
+  describe() { return super.describe() + "B"; }
+}
+class C < B {
+  describe() { return super.describe() + "C"; }
+}
+var result = C().describe();
+"#,
+        "result",
+        LoxValue::String("ABC".into()),
+    );
+}
+
+#[test]
+fn super_binds_this() {
+    assert_global(
+        r#"
+class Base {
+  init(a) { this.a = a; }
+}
+class Derived < Base {
+  init(a, b) {
+    super.init(a);
+    this.b = b;
+  }
+}
+var d = Derived(1, 2);
+var result = d.a + d.b;
+"#,
+        "result",
+        LoxValue::Number(3.0),
+    );
+}
+
+#[test]
+fn super_is_resolved_statically() {
+    assert_global(
+        r#"
+class Base {
+  method() { return "Base"; }
+}
+class Derived < Base {
+  method() { return super.method(); }
+}
+class OtherBase {
+  method() { return "OtherBase"; }
+}
+var derived = Derived();
+Base = OtherBase;
+var result = derived.method();
+"#,
+        "result",
+        LoxValue::String("Base".into()),
+    );
+}
+
+#[test]
+fn superclass_must_be_a_class() {
+    assert_eq!(
+        runtime_error("var NotAClass = 1; class Foo < NotAClass {}"),
+        Some(RuntimeError::SuperclassMustBeAClass(1, 32))
+    );
+}
+
+#[test]
+fn undefined_super_method() {
+    assert_eq!(
+        runtime_error("class A {} class B < A { m() { super.nope(); } } B().m();"),
+        Some(RuntimeError::UndefinedProperty(1, 38, "nope".to_string()))
+    );
+}
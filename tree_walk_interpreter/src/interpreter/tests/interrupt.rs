@@ -0,0 +1,66 @@
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+
+use crate::error::{LoxError, RuntimeError};
+use crate::interpreter::tests::interpret_in;
+use crate::interpreter::value::LoxValue;
+use crate::interpreter::Interpreter;
+
+/// Runs the source, interrupting it from another thread once it has had time to get going.
+fn interrupted(interpreter: &mut Interpreter, source: &str) -> Option<RuntimeError> {
+    let interrupt = interpreter.interrupt_handle();
+    let interrupter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        interrupt.store(true, Ordering::Relaxed);
+    });
+    let result = interpret_in(interpreter, source);
+    interrupter.join().unwrap();
+    match result {
+        Err(LoxError::RuntimeError(e, _)) => Some(e),
+        _ => None,
+    }
+}
+
+#[test]
+fn loops_are_interrupted() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(
+        interrupted(&mut interpreter, "var i = 0;\nwhile (true) i = i + 1;"),
+        Some(RuntimeError::Interrupted(2, 1))
+    );
+}
+
+#[test]
+fn calls_are_interrupted() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(
+        interrupted(
+            &mut interpreter,
+            "fun f(n) { if (n > 0) return f(n - 1); return n; }\nfun g() { f(50); g(); }\ng();"
+        )
+        .map(|e| matches!(e, RuntimeError::Interrupted(..))),
+        Some(true)
+    );
+}
+
+#[test]
+fn interrupted_interpreter_keeps_working() {
+    let mut interpreter = Interpreter::new();
+    interrupted(&mut interpreter, "var a = 1; while (true) {}");
+    interpret_in(
+        &mut interpreter,
+        "var b = a + 1; for (var i = 0; i < 10; i = i + 1) {}",
+    )
+    .unwrap();
+    assert_eq!(interpreter.global("b"), Some(LoxValue::Number(2.0)));
+}
+
+#[test]
+fn interrupts_between_scripts_are_dropped() {
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .interrupt_handle()
+        .store(true, Ordering::Relaxed);
+    interpret_in(&mut interpreter, "while (false) {} fun f() {} f();").unwrap();
+}
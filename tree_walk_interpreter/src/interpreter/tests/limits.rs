@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use crate::error::{LoxError, RuntimeError};
+use crate::interpreter::limits::{Limit, Limits};
+use crate::interpreter::tests::interpret_in;
+use crate::interpreter::value::LoxValue;
+use crate::interpreter::Interpreter;
+
+fn interpret_with(limits: Limits, source: &str) -> Result<Interpreter, LoxError> {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_limits(limits);
+    interpret_in(&mut interpreter, source)?;
+    Ok(interpreter)
+}
+
+fn limit_exceeded(limits: Limits, source: &str) -> Option<RuntimeError> {
+    match interpret_with(limits, source) {
+        Err(LoxError::RuntimeError(e, _)) => Some(e),
+        _ => None,
+    }
+}
+
+#[test]
+fn unlimited_by_default() {
+    let interpreter =
+        interpret_with(Limits::default(), "var i = 0; while (i < 10000) i = i + 1;").unwrap();
+    assert_eq!(interpreter.global("i"), Some(LoxValue::Number(10000.0)));
+}
+
+#[test]
+fn steps() {
+    let limits = Limits {
+        max_steps: Some(100),
+        ..Limits::default()
+    };
+    assert_eq!(
+        limit_exceeded(limits.clone(), "while (true) {}"),
+        Some(RuntimeError::LimitExceeded(1, 1, Limit::Steps))
+    );
+    assert_eq!(
+        limit_exceeded(limits.clone(), "\nfor (;;) {}"),
+        Some(RuntimeError::LimitExceeded(2, 1, Limit::Steps))
+    );
+    assert_eq!(
+        limit_exceeded(limits.clone(), "fun f() { f(); } f();"),
+        Some(RuntimeError::LimitExceeded(1, 13, Limit::Steps))
+    );
+    assert!(interpret_with(limits, "for (var i = 0; i < 50; i = i + 1) {}").is_ok());
+}
+
+#[test]
+fn call_depth() {
+    let limits = Limits {
+        max_call_depth: Some(10),
+        ..Limits::default()
+    };
+    let source = "fun f(n) { if (n > 0) f(n - 1); }\n";
+    assert!(interpret_with(limits.clone(), &format!("{}f(9);", source)).is_ok());
+    assert_eq!(
+        limit_exceeded(limits, &format!("{}f(10);", source)),
+        Some(RuntimeError::LimitExceeded(1, 30, Limit::CallDepth))
+    );
+}
+
+#[test]
+fn heap_objects() {
+    let limits = Limits {
+        max_heap_objects: Some(3),
+        ..Limits::default()
+    };
+    assert_eq!(
+        limit_exceeded(
+            limits.clone(),
+            "class A {}\nvar list = nil;\nwhile (true) list = A();"
+        ),
+        Some(RuntimeError::LimitExceeded(3, 23, Limit::HeapObjects))
+    );
+    assert_eq!(
+        limit_exceeded(limits, "var s = \"\"; while (true) s = s + \"a\";"),
+        Some(RuntimeError::LimitExceeded(1, 32, Limit::HeapObjects))
+    );
+}
+
+#[test]
+fn string_length() {
+    let limits = Limits {
+        max_string_len: Some(8),
+        ..Limits::default()
+    };
+    assert!(interpret_with(limits.clone(), "var s = \"abcd\" + \"efgh\";").is_ok());
+    assert_eq!(
+        limit_exceeded(limits, "var s = \"abcd\" + \"efghi\";"),
+        Some(RuntimeError::LimitExceeded(1, 16, Limit::StringLength))
+    );
+}
+
+#[test]
+fn wall_clock_timeout() {
+    let limits = Limits {
+        wall_clock_timeout: Some(Duration::from_millis(50)),
+        ..Limits::default()
+    };
+    assert_eq!(
+        limit_exceeded(limits, "while (true) {}"),
+        Some(RuntimeError::LimitExceeded(1, 1, Limit::WallClockTimeout))
+    );
+}
+
+#[test]
+fn budget_is_per_run() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_limits(Limits {
+        max_steps: Some(60),
+        ..Limits::default()
+    });
+    for _ in 0..3 {
+        assert!(interpret_in(&mut interpreter, "for (var i = 0; i < 50; i = i + 1) {}").is_ok());
+    }
+}
+
+#[test]
+fn message() {
+    let limits = Limits {
+        max_steps: Some(0),
+        ..Limits::default()
+    };
+    let error = interpret_with(limits, "while (true) {}").err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "Exceeded the step limit.\n[line 1] in script"
+    );
+}
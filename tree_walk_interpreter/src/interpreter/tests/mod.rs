@@ -0,0 +1,50 @@
+use crate::error::{LoxError, LoxResult, RuntimeError};
+use crate::interpreter::value::LoxValue;
+use crate::interpreter::Interpreter;
+use crate::parser::parse;
+use crate::resolver::resolve;
+use crate::scanner::scan;
+
+mod classes;
+mod closures;
+mod expressions;
+mod inheritance;
+mod interrupt;
+mod limits;
+mod natives;
+mod recursion;
+mod stack_trace;
+
+/// Runs the source in a fresh interpreter so its globals can be inspected afterwards.
+fn interpret(source: &str) -> LoxResult<Interpreter> {
+    let mut interpreter = Interpreter::new();
+    interpret_in(&mut interpreter, source)?;
+    Ok(interpreter)
+}
+
+/// Runs the source in an interpreter the test has set up, such as one with limits.
+fn interpret_in(interpreter: &mut Interpreter, source: &str) -> LoxResult<()> {
+    let statements = parse(&scan(source)).map_err(|mut e| e.remove(0))?;
+    let locals = resolve(&statements).map_err(|mut e| e.remove(0))?;
+    interpreter.interpret(&statements, locals)
+}
+
+/// Runs the source, expecting it to fail with a runtime error.
+fn runtime_error(source: &str) -> Option<RuntimeError> {
+    match interpret(source) {
+        Err(LoxError::RuntimeError(e, _)) => Some(e),
+        _ => None,
+    }
+}
+
+fn global_string(interpreter: &Interpreter, name: &str) -> String {
+    interpreter
+        .global(name)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| panic!("global {} is not defined", name))
+}
+
+fn assert_global(source: &str, name: &str, expected: LoxValue) {
+    let interpreter = interpret(source).unwrap();
+    assert_eq!(interpreter.global(name), Some(expected));
+}
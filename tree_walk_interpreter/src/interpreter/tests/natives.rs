@@ -0,0 +1,110 @@
+use crate::error::{LoxError, NativeError, RuntimeError};
+use crate::interpreter::native::{NativeFunction, NativeRegistry};
+use crate::interpreter::tests::{global_string, interpret, interpret_in, runtime_error};
+use crate::interpreter::value::LoxValue;
+use crate::interpreter::Interpreter;
+
+fn interpret_with(registry: &NativeRegistry, source: &str) -> Result<Interpreter, LoxError> {
+    let mut interpreter = Interpreter::new();
+    interpreter.define_natives(registry);
+    interpret_in(&mut interpreter, source)?;
+    Ok(interpreter)
+}
+
+#[test]
+fn clock_is_defined_by_default() {
+    let interpreter = interpret("var before = clock(); var printed = clock;").unwrap();
+    assert!(matches!(interpreter.global("before"), Some(LoxValue::Number(n)) if n > 0.0));
+    assert_eq!(global_string(&interpreter, "printed"), "<native fn>");
+}
+
+#[test]
+fn native_arity_is_checked() {
+    assert_eq!(
+        runtime_error("clock(1);"),
+        Some(RuntimeError::ArityMismatch(1, 8, 0, 1))
+    );
+}
+
+#[test]
+fn typed_closures() {
+    let mut registry = NativeRegistry::new();
+    registry
+        .register_fn("add", |a: f64, b: f64| a + b)
+        .register_fn("shout", |s: String| s.to_uppercase())
+        .register_fn("not", |b: bool| !b)
+        .register_fn("ignore", |_: LoxValue| ());
+
+    let interpreter = interpret_with(
+        &registry,
+        r#"
+var sum = add(1, 2);
+var loud = shout("hi");
+var negated = not(true);
+var nothing = ignore(sum);
+"#,
+    )
+    .unwrap();
+    assert_eq!(interpreter.global("sum"), Some(LoxValue::Number(3.0)));
+    assert_eq!(interpreter.global("loud"), Some(LoxValue::from("HI")));
+    assert_eq!(interpreter.global("negated"), Some(LoxValue::Bool(false)));
+    assert_eq!(interpreter.global("nothing"), Some(LoxValue::Nil));
+}
+
+#[test]
+fn argument_type_errors() {
+    let mut registry = NativeRegistry::new();
+    registry.register_fn("add", |a: f64, b: f64| a + b);
+
+    match interpret_with(&registry, "add(1, \"two\");") {
+        Err(LoxError::RuntimeError(error, _)) => assert_eq!(
+            error,
+            RuntimeError::NativeFunction(
+                1,
+                13,
+                "add".to_string(),
+                NativeError::ArgumentType(2, "a number", "two".to_string())
+            )
+        ),
+        _ => panic!("expected a runtime error"),
+    }
+}
+
+#[test]
+fn fallible_closures() {
+    let mut registry = NativeRegistry::new();
+    registry.register_fn("fail", || -> Result<f64, NativeError> {
+        Err(NativeError::Failed("nope".to_string()))
+    });
+
+    let error = interpret_with(&registry, "fail();").err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "Error in native function 'fail': nope\n[line 1] in fail()\n[line 1] in script"
+    );
+}
+
+struct Counter;
+
+impl NativeFunction for Counter {
+    fn name(&self) -> &str {
+        "count"
+    }
+
+    fn arity(&self) -> usize {
+        2
+    }
+
+    fn call(&self, arguments: &[LoxValue]) -> Result<LoxValue, NativeError> {
+        Ok(LoxValue::Number(arguments.len() as f64))
+    }
+}
+
+#[test]
+fn trait_implementations() {
+    let mut registry = NativeRegistry::new();
+    registry.register(Counter);
+
+    let interpreter = interpret_with(&registry, "var result = count(nil, nil);").unwrap();
+    assert_eq!(interpreter.global("result"), Some(LoxValue::Number(2.0)));
+}
@@ -0,0 +1,65 @@
+use crate::error::{LoxError, RuntimeError};
+use crate::interpreter::tests::{assert_global, interpret_in};
+use crate::interpreter::value::LoxValue;
+use crate::interpreter::Interpreter;
+
+const COUNT_DOWN: &str = r#"
+fun count(n) {
+  if (n == 0) return 0;
+  return 1 + count(n - 1);
+}
+var result = count(DEPTH);
+"#;
+
+fn run_with_max_call_depth(source: &str, max_call_depth: usize) -> Result<(), LoxError> {
+    interpret_in(
+        &mut Interpreter::with_max_call_depth(max_call_depth),
+        source,
+    )
+}
+
+#[test]
+fn deep_recursion_does_not_overflow_the_native_stack() {
+    assert_global(
+        &COUNT_DOWN.replace("DEPTH", "5000"),
+        "result",
+        LoxValue::Number(5000.0),
+    );
+}
+
+#[test]
+fn call_depth_limit() {
+    let source = COUNT_DOWN.replace("DEPTH", "50");
+    assert_eq!(run_with_max_call_depth(&source, 51), Ok(()));
+
+    match run_with_max_call_depth(&source, 50) {
+        Err(LoxError::RuntimeError(error, stack_trace)) => {
+            assert_eq!(error, RuntimeError::StackOverflow(4, 25));
+            assert_eq!(stack_trace.0.len(), 51);
+        }
+        result => panic!("expected a stack overflow, got {:?}", result),
+    }
+}
+
+#[test]
+fn unbounded_recursion() {
+    let source = "fun f() { f(); } f();";
+    match run_with_max_call_depth(source, 1000) {
+        Err(LoxError::RuntimeError(error, _)) => {
+            assert_eq!(error, RuntimeError::StackOverflow(1, 13))
+        }
+        result => panic!("expected a stack overflow, got {:?}", result),
+    }
+}
+
+#[test]
+fn long_stack_traces_are_elided() {
+    let source = "fun f() { f(); } f();";
+    let error = run_with_max_call_depth(source, 100)
+        .unwrap_err()
+        .to_string();
+    let lines: Vec<&str> = error.lines().collect();
+    assert_eq!(lines[0], "Stack overflow.");
+    assert_eq!(lines[11], "... 81 more frames");
+    assert_eq!(lines.len(), 22);
+}
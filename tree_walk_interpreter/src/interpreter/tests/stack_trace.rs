@@ -0,0 +1,82 @@
+use crate::error::{LoxError, RuntimeError, StackFrame, StackTrace};
+use crate::interpreter::tests::interpret;
+
+fn stack_trace(source: &str) -> StackTrace {
+    match interpret(source) {
+        Err(LoxError::RuntimeError(_, stack_trace)) => stack_trace,
+        _ => panic!("expected a runtime error"),
+    }
+}
+
+fn frame(function: Option<&str>, line: usize) -> StackFrame {
+    StackFrame {
+        function: function.map(|f| f.to_string()),
+        line,
+    }
+}
+
+#[test]
+fn top_level_error() {
+    assert_eq!(
+        stack_trace("var a = 1;\nprint -\"a\";"),
+        StackTrace(vec![frame(None, 2)])
+    );
+}
+
+#[test]
+fn nested_calls() {
+    let source = r#"
+fun inner() {
+  return nil + 1;
+}
+
+fun outer() {
+  return inner();
+}
+
+outer();
+"#;
+    assert_eq!(
+        stack_trace(source),
+        StackTrace(vec![
+            frame(Some("inner"), 3),
+            frame(Some("outer"), 7),
+            frame(None, 10),
+        ])
+    );
+}
+
+#[test]
+fn initializer_frames_are_named_after_the_class() {
+    let source = "class Foo {\n  init() {\n    this.a = nope;\n  }\n}\nFoo();";
+    assert_eq!(
+        stack_trace(source),
+        StackTrace(vec![frame(Some("Foo"), 3), frame(None, 6)])
+    );
+}
+
+#[test]
+fn printed_like_a_traceback() {
+    let source = "fun f() {\n  nope;\n}\nf();";
+    assert_eq!(
+        interpret(source).err().unwrap().to_string(),
+        "Undefined variable 'nope'.\n[line 2] in f()\n[line 4] in script"
+    );
+}
+
+#[test]
+fn call_stack_is_unwound_after_an_error() {
+    let mut interpreter = interpret("fun f() { nope; }").unwrap();
+    let statements = crate::parser::parse(&crate::scanner::scan("f();")).unwrap();
+    let locals = crate::resolver::resolve(&statements).unwrap();
+    assert!(interpreter.interpret(&statements, locals.clone()).is_err());
+
+    let error = interpreter.interpret(&statements, locals).unwrap_err();
+    assert_eq!(
+        error,
+        LoxError::RuntimeError(
+            RuntimeError::UndefinedVariable(1, 11, "nope".to_string()),
+            StackTrace(vec![frame(Some("f"), 1), frame(None, 1)])
+        )
+    );
+}
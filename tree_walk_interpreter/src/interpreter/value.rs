@@ -0,0 +1,71 @@
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+use crate::interpreter::class::{LoxClass, LoxInstance};
+use crate::interpreter::function::LoxFunction;
+use crate::interpreter::native::NativeFunction;
+use crate::parser::ast::Literal;
+
+/// A runtime Lox value.
+#[derive(Clone, Debug)]
+pub enum LoxValue {
+    Nil,
+    Bool(bool),
+    Number(f64),
+    String(Rc<str>),
+    Function(Rc<LoxFunction>),
+    NativeFunction(Rc<dyn NativeFunction>),
+    Class(Rc<LoxClass>),
+    Instance(Rc<RefCell<LoxInstance>>),
+}
+
+impl LoxValue {
+    /// `nil` and `false` are falsey, everything else is truthy.
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, LoxValue::Nil | LoxValue::Bool(false))
+    }
+}
+
+impl PartialEq for LoxValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (LoxValue::Nil, LoxValue::Nil) => true,
+            (LoxValue::Bool(a), LoxValue::Bool(b)) => a == b,
+            (LoxValue::Number(a), LoxValue::Number(b)) => a == b,
+            (LoxValue::String(a), LoxValue::String(b)) => a == b,
+            // Functions, classes and instances are only equal to themselves.
+            (LoxValue::Function(a), LoxValue::Function(b)) => Rc::ptr_eq(a, b),
+            (LoxValue::NativeFunction(a), LoxValue::NativeFunction(b)) => Rc::ptr_eq(a, b),
+            (LoxValue::Class(a), LoxValue::Class(b)) => Rc::ptr_eq(a, b),
+            (LoxValue::Instance(a), LoxValue::Instance(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Display for LoxValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoxValue::Nil => write!(f, "nil"),
+            LoxValue::Bool(b) => write!(f, "{}", b),
+            LoxValue::Number(n) => write!(f, "{}", n),
+            LoxValue::String(s) => write!(f, "{}", s),
+            LoxValue::Function(function) => write!(f, "{}", function),
+            LoxValue::NativeFunction(_) => write!(f, "<native fn>"),
+            LoxValue::Class(class) => write!(f, "{}", class),
+            LoxValue::Instance(instance) => write!(f, "{}", instance.borrow()),
+        }
+    }
+}
+
+impl From<&Literal> for LoxValue {
+    fn from(literal: &Literal) -> Self {
+        match literal {
+            Literal::Nil => LoxValue::Nil,
+            Literal::Bool(b) => LoxValue::Bool(*b),
+            Literal::Number(n) => LoxValue::Number(*n),
+            Literal::String(s) => LoxValue::String(s.as_str().into()),
+        }
+    }
+}
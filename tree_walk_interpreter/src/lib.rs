@@ -0,0 +1,40 @@
+//! A tree walking interpreter for Lox, the language from
+//! [Crafting Interpreters](https://craftinginterpreters.com).
+//!
+//! Embedders should only need [Lox], which runs scripts in a session whose globals persist from
+//! one script to the next:
+//!
+//! ```
+//! use darmok_lox::{Lox, LoxValue};
+//!
+//! let mut lox = Lox::new();
+//! lox.define_global("greeting", "Hello");
+//! lox.eval(r#"fun greet(name) { return greeting + ", " + name + "!"; }"#).unwrap();
+//! let greeting = lox.call("greet", vec!["Lox".into()]).unwrap();
+//! assert_eq!(greeting, LoxValue::from("Hello, Lox!"));
+//! ```
+//!
+//! The [linter], the [repl] and [format_source] are the tools the `darmok_lox` command line is
+//! built from.
+
+pub use crate::error::{LoxError, LoxResult};
+pub use crate::formatter::{format_range, format_source};
+pub use crate::interpreter::limits::{Limit, Limits};
+pub use crate::interpreter::native::{NativeFunction, NativeRegistry};
+pub use crate::interpreter::value::LoxValue;
+pub use crate::interpreter::DEFAULT_MAX_CALL_DEPTH;
+pub use crate::output::CapturedOutput;
+pub use crate::scanner::tokens::{LoxToken, TokenType};
+pub use crate::session::Lox;
+
+pub mod error;
+mod formatter;
+mod interpreter;
+pub mod linter;
+mod output;
+mod parser;
+pub mod repl;
+mod resolver;
+mod scanner;
+mod session;
+mod utils;
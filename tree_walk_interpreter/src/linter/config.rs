@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::linter::naming::Case;
+use crate::linter::rule::{LintRule, Severity};
+use crate::utils::{load_toml, parse_toml};
+use crate::LoxResult;
+
+/// Linter settings, usually read from a `.loxlint.toml`:
+///
+/// ```toml
+/// indent_width = 2
+///
+/// [naming]
+/// variables = "snake_case"
+///
+/// [rules]
+/// no-tabs = "error"
+/// double-space = "off"
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    /// Indentation has to be a multiple of this many spaces.
+    pub indent_width: usize,
+    pub naming: NamingConfig,
+    /// Severity of each rule by id, rules that aren't listed keep their default severity.
+    pub rules: BTreeMap<String, Severity>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig {
+            indent_width: 4,
+            naming: NamingConfig::default(),
+            rules: BTreeMap::new(),
+        }
+    }
+}
+
+/// The convention each kind of name has to follow.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NamingConfig {
+    pub classes: Case,
+    pub functions: Case,
+    pub variables: Case,
+}
+
+impl Default for NamingConfig {
+    fn default() -> Self {
+        NamingConfig {
+            classes: Case::UpperCamelCase,
+            functions: Case::LowerCamelCase,
+            variables: Case::LowerCamelCase,
+        }
+    }
+}
+
+impl LintConfig {
+    pub const FILE_NAME: &'static str = ".loxlint.toml";
+
+    /// Reads the config at `path`, without a file there every rule keeps its default severity.
+    pub fn load(path: impl AsRef<Path>) -> LoxResult<LintConfig> {
+        load_toml(path)
+    }
+
+    pub fn parse(path: impl AsRef<Path>, source: &str) -> LoxResult<LintConfig> {
+        parse_toml(path, source)
+    }
+
+    pub fn severity(&self, rule: &dyn LintRule) -> Severity {
+        self.rules
+            .get(rule.id())
+            .copied()
+            .unwrap_or_else(|| rule.default_severity())
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::{Path, PathBuf};
+
+use ignore::WalkBuilder;
+
+use crate::LoxError;
+
+/// Name of the files listing what not to lint, in the same syntax as `.gitignore`.  Like a
+/// `.gitignore` one applies to its own directory and everything below it.
+pub const IGNORE_FILE: &str = ".loxlintignore";
+
+const EXTENSION: &str = "lox";
+
+/// The scripts to lint for each of `paths`: a file is always linted, a directory is searched for
+/// `*.lox` files that aren't ignored by a [IGNORE_FILE] or `.gitignore`.  Each directory's files
+/// come out sorted by name.  Paths that don't exist or can't be searched are returned alongside
+/// the files, so the rest can still be linted.
+pub fn lox_files(paths: &[impl AsRef<Path>]) -> (Vec<PathBuf>, Vec<(String, LoxError)>) {
+    let mut files = Vec::new();
+    let mut failures = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        let walk = WalkBuilder::new(path)
+            .add_custom_ignore_filename(IGNORE_FILE)
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+        for entry in walk {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    let path = failed_path(&e).unwrap_or(path).display().to_string();
+                    let message = e
+                        .io_error()
+                        .map_or_else(|| e.to_string(), |e| e.to_string());
+                    failures.push((path.clone(), LoxError::IoError(path, message)));
+                    continue;
+                }
+            };
+            let is_file = entry.file_type().is_some_and(|t| t.is_file());
+            let is_script = entry.path().extension().is_some_and(|e| e == EXTENSION);
+            if is_file && (entry.depth() == 0 || is_script) {
+                files.push(entry.into_path());
+            }
+        }
+    }
+    (files, failures)
+}
+
+/// The file or directory a walk error is about, which may be below the path being searched.
+fn failed_path(error: &ignore::Error) -> Option<&Path> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            failed_path(err)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use crate::scanner::scan_with_whitespace;
+
+/// A machine-applicable edit that fixes a lint: the source from `start` up to (not including)
+/// `end` is replaced.  Both are the `(line, column)` of a token, or of the end of the source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fix {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+    pub replacement: String,
+}
+
+/// Applies the fixes that don't overlap one applied before them, returning the fixed source and
+/// how many were applied.  Nothing is applied to a source that doesn't scan, since its tokens'
+/// positions can't be mapped back to it.
+pub fn apply_fixes<'a>(source: &str, fixes: impl IntoIterator<Item = &'a Fix>) -> (String, usize) {
+    let offsets = match token_offsets(source) {
+        Some(offsets) => offsets,
+        None => return (source.to_string(), 0),
+    };
+
+    let mut edits: Vec<(usize, usize, &str)> = fixes
+        .into_iter()
+        .filter_map(|fix| {
+            let start = *offsets.get(&fix.start)?;
+            let end = *offsets.get(&fix.end)?;
+            (start <= end).then_some((start, end, fix.replacement.as_str()))
+        })
+        .collect();
+    edits.sort_by_key(|&(start, end, _)| (start, end));
+
+    let mut fixed = String::with_capacity(source.len());
+    let mut copied = 0;
+    let mut applied = 0;
+    for (start, end, replacement) in edits {
+        if start < copied {
+            continue;
+        }
+        fixed.push_str(&source[copied..start]);
+        fixed.push_str(replacement);
+        copied = end;
+        applied += 1;
+    }
+    fixed.push_str(&source[copied..]);
+    (fixed, applied)
+}
+
+/// Byte offset of every token's position, including the end of the source.
+fn token_offsets(source: &str) -> Option<HashMap<(usize, usize), usize>> {
+    let mut offsets = HashMap::new();
+    let mut offset = 0;
+    for token in scan_with_whitespace(source, false) {
+        let token = token.ok()?;
+        offsets.insert((token.line, token.column), offset);
+        offset += token.lexeme.len();
+    }
+    // Lexemes that aren't exactly the source they were scanned from would put edits in the wrong
+    // place.
+    (offset == source.len()).then_some(offsets)
+}
@@ -1,50 +1,215 @@
-use std::fs::read_to_string;
-
-use crate::{scan, LinterError, LoxError, LoxResult, LoxToken, TokenType};
-
-#[cfg(test)]
-mod tests;
-
-// TODO linting mode: multiple steps, first just reads token streams for token rules (double space not after newline, use of tabs, etc)
-// > handle tab (warning no tabs use spaces)
-// > handle non newline whitespace with more than one space (linter warning)
-// > warn there should be no \r\n on linux
-pub fn lint(file_name: &str) -> anyhow::Result<Vec<LoxError>> {
-    let script = read_to_string(file_name)?;
-    let tokens = scan(&script);
-
-    // This obviously not complete, just serves as an example
-    let tokenizer_lint_errors = lint_tokens(&tokens);
-
-    // let parser_lint_errors = lint(parse(tokens))?;
-    // then print all
-
-    // Append all types of lint errors
-    Ok(tokenizer_lint_errors)
-}
-
-pub fn lint_tokens(tokens: &[LoxResult<LoxToken>]) -> Vec<LoxError> {
-    // Finds double spaces.  Wouldn't even really work because it doesn't detect if they're on a blank line or more than two.
-    tokens
-        .windows(2)
-        .filter(|w| {
-            matches!(
-                w,
-                [
-                    Ok(LoxToken {
-                        token_type: TokenType::Space,
-                        ..
-                    }),
-                    Ok(LoxToken {
-                        token_type: TokenType::Space,
-                        ..
-                    })
-                ]
-            )
-        })
-        .map(|w| {
-            let first_space = w.get(0).cloned().unwrap().unwrap();
-            LinterError::DoubleSpaceDetected(first_space.line, first_space.column).into()
-        })
-        .collect()
-}
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::error::{LoxError, LoxResult};
+use crate::linter::semantic::ScopeAnalysis;
+use crate::parser::parse;
+use crate::scanner::scan_with_whitespace;
+use crate::scanner::tokens::{LoxToken, TokenType};
+
+pub use config::{LintConfig, NamingConfig};
+pub use files::{lox_files, IGNORE_FILE};
+pub use fix::{apply_fixes, Fix};
+pub use naming::Case;
+pub use registry::LintRegistry;
+pub use report::{LintReport, OutputFormat};
+pub use rule::{Lint, LintContext, LintRule, Severity};
+
+mod config;
+mod files;
+mod fix;
+mod naming;
+mod registry;
+mod report;
+mod rule;
+mod semantic;
+mod whitespace;
+
+#[cfg(test)]
+mod tests;
+
+/// Fixing a finding can reveal another one (a tab turned into spaces might be trailing
+/// whitespace), fixes are applied this many times at most.
+const MAX_FIX_ROUNDS: usize = 10;
+
+/// Comment that suppresses findings on the line after it, of every rule or just the ones listed.
+const DISABLE_NEXT_LINE: &str = "lint-disable-next-line";
+
+/// Runs the rules of a registry, with the severities of a config.
+pub struct Linter {
+    registry: LintRegistry,
+    config: LintConfig,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Linter {
+            registry: LintRegistry::standard(),
+            config: LintConfig::default(),
+        }
+    }
+}
+
+impl Linter {
+    /// Fails if the config sets the severity of a rule that isn't registered.
+    pub fn new(registry: LintRegistry, config: LintConfig) -> LoxResult<Linter> {
+        if let Some(id) = config.rules.keys().find(|id| registry.get(id).is_none()) {
+            return Err(LoxError::InvalidConfig(
+                LintConfig::FILE_NAME.to_string(),
+                format!("unknown rule '{}'", id),
+            ));
+        }
+        Ok(Linter { registry, config })
+    }
+
+    pub fn lint_file(&self, path: impl AsRef<Path>) -> LoxResult<Vec<Lint>> {
+        let path = path.as_ref();
+        let source = read_to_string(path)
+            .map_err(|e| LoxError::IoError(path.display().to_string(), e.to_string()))?;
+        Ok(self.lint(&source))
+    }
+
+    /// Lints the files in parallel, the report lists them in the order they were given.  A file
+    /// that can't be read is reported as a failure, the rest are still linted.
+    pub fn lint_files(&self, paths: &[PathBuf]) -> LintReport {
+        let results: Vec<LoxResult<Vec<Lint>>> =
+            paths.par_iter().map(|path| self.lint_file(path)).collect();
+        let mut report = LintReport::new();
+        for (path, result) in paths.iter().zip(results) {
+            let path = path.display().to_string();
+            match result {
+                Ok(lints) => report.add(path, lints),
+                Err(error) => report.fail(path, error),
+            }
+        }
+        report
+    }
+
+    /// Rewrites the file with its findings fixed, returning how many fixes were applied.
+    pub fn fix_file(&self, path: impl AsRef<Path>) -> LoxResult<usize> {
+        let path = path.as_ref();
+        let io_error =
+            |e: std::io::Error| LoxError::IoError(path.display().to_string(), e.to_string());
+        let source = read_to_string(path).map_err(io_error)?;
+        let (fixed, applied) = self.fix(&source);
+        if applied > 0 {
+            std::fs::write(path, fixed).map_err(io_error)?;
+        }
+        Ok(applied)
+    }
+
+    /// Fixes the files in parallel, returning how many fixes were applied to each or why it
+    /// couldn't be fixed.
+    pub fn fix_files(&self, paths: &[PathBuf]) -> Vec<LoxResult<usize>> {
+        paths.par_iter().map(|path| self.fix_file(path)).collect()
+    }
+
+    pub fn lint(&self, source: &str) -> Vec<Lint> {
+        self.lint_tokens(&scan_with_whitespace(source, false))
+    }
+
+    /// Applies the fixes of every finding, returning the fixed source and how many were applied.
+    pub fn fix(&self, source: &str) -> (String, usize) {
+        let mut source = source.to_string();
+        let mut total = 0;
+        for _ in 0..MAX_FIX_ROUNDS {
+            let lints = self.lint(&source);
+            let (fixed, applied) =
+                apply_fixes(&source, lints.iter().filter_map(|l| l.fix.as_ref()));
+            if applied == 0 {
+                break;
+            }
+            source = fixed;
+            total += applied;
+        }
+        (source, total)
+    }
+
+    /// Lints tokens scanned with their whitespace kept, sorted by position.  Tokens that failed to
+    /// scan are skipped and rules that need the parsed program are skipped if it doesn't parse,
+    /// reporting those errors is left to the interpreter.
+    pub fn lint_tokens(&self, tokens: &[LoxResult<LoxToken>]) -> Vec<Lint> {
+        let statements = parse(tokens).ok();
+        let scopes = statements.as_deref().map(ScopeAnalysis::of);
+        let mut tokens: Vec<LoxToken> = tokens
+            .iter()
+            .filter_map(|t| t.as_ref().ok())
+            .cloned()
+            .collect();
+        let end = match tokens.pop() {
+            Some(eof) if eof.token_type == TokenType::Eof => (eof.line, eof.column),
+            Some(last) => {
+                let end = (last.line, last.column + last.lexeme.len());
+                tokens.push(last);
+                end
+            }
+            None => (1, 1),
+        };
+        let context = LintContext {
+            tokens: &tokens,
+            statements: statements.as_deref(),
+            scopes: scopes.as_ref(),
+            end,
+            config: &self.config,
+        };
+        let suppressions = suppressions(&tokens);
+
+        let mut lints: Vec<Lint> = self
+            .registry
+            .rules()
+            .filter_map(|rule| match self.config.severity(rule) {
+                Severity::Off => None,
+                severity => Some((rule, severity)),
+            })
+            .flat_map(|(rule, severity)| {
+                rule.check(&context).into_iter().map(move |error| Lint {
+                    rule: rule.id(),
+                    severity,
+                    fix: rule.fix(&error, &context),
+                    error,
+                })
+            })
+            .filter(|lint| !is_suppressed(&suppressions, lint))
+            .collect();
+        lints.sort_by_key(|lint| lint.error.span());
+        lints
+    }
+}
+
+/// Lines with findings suppressed, and the rules suppressed on them (all of them if empty).
+fn suppressions(tokens: &[LoxToken]) -> HashMap<usize, Vec<String>> {
+    tokens
+        .iter()
+        .filter(|t| t.token_type == TokenType::Comment)
+        .filter_map(|t| {
+            let comment = t.lexeme.trim_start_matches('/').trim();
+            let rules = comment.strip_prefix(DISABLE_NEXT_LINE)?;
+            let rules = rules
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|rule| !rule.is_empty())
+                .map(str::to_string)
+                .collect();
+            Some((t.line + 1, rules))
+        })
+        .collect()
+}
+
+fn is_suppressed(suppressions: &HashMap<usize, Vec<String>>, lint: &Lint) -> bool {
+    let (line, _) = lint.error.span();
+    suppressions
+        .get(&line)
+        .is_some_and(|rules| rules.is_empty() || rules.iter().any(|r| r == lint.rule))
+}
+
+/// Runs the standard rules with their default settings over tokens scanned with their whitespace
+/// kept.
+pub fn lint_tokens(tokens: &[LoxResult<LoxToken>]) -> Vec<LoxError> {
+    Linter::default()
+        .lint_tokens(tokens)
+        .into_iter()
+        .map(|lint| lint.error.into())
+        .collect()
+}
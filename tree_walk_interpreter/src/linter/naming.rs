@@ -0,0 +1,193 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Deserialize;
+
+use crate::error::LinterError;
+use crate::linter::config::LintConfig;
+use crate::linter::rule::{LintContext, LintRule, Severity};
+use crate::scanner::tokens::TokenType;
+
+/// A naming convention, written in configs the way it looks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum Case {
+    UpperCamelCase,
+    #[serde(rename = "lowerCamelCase")]
+    LowerCamelCase,
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    #[serde(rename = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnakeCase,
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Case::UpperCamelCase => write!(f, "UpperCamelCase"),
+            Case::LowerCamelCase => write!(f, "lowerCamelCase"),
+            Case::SnakeCase => write!(f, "snake_case"),
+            Case::ScreamingSnakeCase => write!(f, "SCREAMING_SNAKE_CASE"),
+        }
+    }
+}
+
+impl Case {
+    /// Whether the name follows the convention, ignoring leading underscores.
+    pub fn matches(self, name: &str) -> bool {
+        let name = name.trim_start_matches('_');
+        let first = match name.chars().next() {
+            Some(first) => first,
+            None => return true,
+        };
+        match self {
+            Case::UpperCamelCase => first.is_ascii_uppercase() && !name.contains('_'),
+            Case::LowerCamelCase => !first.is_ascii_uppercase() && !name.contains('_'),
+            Case::SnakeCase => !name.chars().any(|c| c.is_ascii_uppercase()),
+            Case::ScreamingSnakeCase => !name.chars().any(|c| c.is_ascii_lowercase()),
+        }
+    }
+
+    /// The name rewritten to follow the convention, leading underscores are kept as they are.
+    pub fn convert(self, name: &str) -> String {
+        let trimmed = name.trim_start_matches('_');
+        let prefix = &name[..name.len() - trimmed.len()];
+        let words = words(trimmed);
+        let converted = match self {
+            Case::UpperCamelCase => words.iter().map(|w| capitalized(w)).collect(),
+            Case::LowerCamelCase => words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| {
+                    if i == 0 {
+                        w.to_lowercase()
+                    } else {
+                        capitalized(w)
+                    }
+                })
+                .collect(),
+            Case::SnakeCase => words
+                .iter()
+                .map(|w| w.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Case::ScreamingSnakeCase => words
+                .iter()
+                .map(|w| w.to_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+        };
+        format!("{}{}", prefix, converted)
+    }
+}
+
+/// Splits an identifier at underscores and case changes, keeping acronyms such as the `HTTP` in
+/// `HTTPServer` together.
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let previous = i.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(i + 1);
+        let starts_word = c.is_ascii_uppercase()
+            && previous.is_some_and(|p| {
+                p.is_ascii_lowercase()
+                    || p.is_ascii_digit()
+                    || (p.is_ascii_uppercase() && next.is_some_and(|n| n.is_ascii_lowercase()))
+            });
+        if starts_word && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn capitalized(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + &chars.as_str().to_lowercase(),
+        None => String::new(),
+    }
+}
+
+/// Names declared with a keyword (`class`, `fun` or `var`) have to follow the configured
+/// convention.  Only the tokens are looked at, so methods and parameters aren't checked.
+pub struct Naming {
+    id: &'static str,
+    keyword: TokenType,
+    kind: &'static str,
+    case: fn(&LintConfig) -> Case,
+}
+
+impl Naming {
+    pub fn classes() -> Naming {
+        Naming {
+            id: "class-naming",
+            keyword: TokenType::Class,
+            kind: "Class",
+            case: |config| config.naming.classes,
+        }
+    }
+
+    pub fn functions() -> Naming {
+        Naming {
+            id: "function-naming",
+            keyword: TokenType::Fun,
+            kind: "Function",
+            case: |config| config.naming.functions,
+        }
+    }
+
+    pub fn variables() -> Naming {
+        Naming {
+            id: "variable-naming",
+            keyword: TokenType::Var,
+            kind: "Variable",
+            case: |config| config.naming.variables,
+        }
+    }
+}
+
+impl LintRule for Naming {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        let case = (self.case)(context.config);
+        let significant: Vec<_> = context
+            .tokens
+            .iter()
+            .filter(|t| !t.is_whitespace() && !t.is_comment())
+            .collect();
+        significant
+            .windows(2)
+            .filter(|pair| pair[0].token_type == self.keyword)
+            .map(|pair| pair[1])
+            .filter(|name| name.token_type == TokenType::Identifier && !case.matches(&name.lexeme))
+            .map(|name| {
+                LinterError::NamingConvention(
+                    name.line,
+                    name.column,
+                    self.kind,
+                    name.lexeme.clone(),
+                    case,
+                    case.convert(&name.lexeme),
+                )
+            })
+            .collect()
+    }
+}
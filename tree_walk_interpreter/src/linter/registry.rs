@@ -0,0 +1,56 @@
+use crate::linter::naming::Naming;
+use crate::linter::rule::LintRule;
+use crate::linter::semantic::{
+    ConstantComparison, SelfAssignment, Shadowing, UnreachableCode, UnusedParameter, UnusedVariable,
+};
+use crate::linter::whitespace::{
+    DoubleSpace, IndentationWidth, LineEndings, NoTabs, TrailingWhitespace,
+};
+
+/// The rules a [Linter](crate::linter::Linter) runs, in the order their findings are reported
+/// when several are at the same position.
+#[derive(Default)]
+pub struct LintRegistry {
+    rules: Vec<Box<dyn LintRule>>,
+}
+
+impl LintRegistry {
+    /// An empty registry, without even the standard rules.
+    pub fn new() -> LintRegistry {
+        LintRegistry::default()
+    }
+
+    /// Every rule that comes with the linter.
+    pub fn standard() -> LintRegistry {
+        let mut registry = LintRegistry::new();
+        registry
+            .register(LineEndings)
+            .register(NoTabs)
+            .register(IndentationWidth)
+            .register(TrailingWhitespace)
+            .register(DoubleSpace)
+            .register(UnusedVariable)
+            .register(UnusedParameter)
+            .register(Shadowing)
+            .register(UnreachableCode)
+            .register(SelfAssignment)
+            .register(ConstantComparison)
+            .register(Naming::classes())
+            .register(Naming::functions())
+            .register(Naming::variables());
+        registry
+    }
+
+    pub fn register(&mut self, rule: impl LintRule + 'static) -> &mut LintRegistry {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &dyn LintRule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn LintRule> {
+        self.rules().find(|rule| rule.id() == id)
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Component, Path};
+use std::str::FromStr;
+
+use serde_json::{json, Value};
+
+use crate::error::{LinterError, LoxError};
+use crate::linter::rule::{Lint, Severity};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+/// Base that relative artifact URIs are resolved against, the directory the linter was run in.
+const SARIF_SRCROOT: &str = "%SRCROOT%";
+const CHECKSTYLE_VERSION: &str = "4.3";
+
+/// How lint results are rendered.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// One `file: severity: message [rule]` line per finding.
+    Text,
+    /// SARIF 2.1.0 JSON, as read by code scanning tools.
+    Sarif,
+    /// Checkstyle XML, as read by CI servers.
+    Checkstyle,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "checkstyle" => Ok(OutputFormat::Checkstyle),
+            _ => Err(format!(
+                "unknown format '{}', expected text, sarif or checkstyle",
+                s
+            )),
+        }
+    }
+}
+
+/// The findings of a lint run, per file in the order the files were linted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LintReport {
+    pub files: Vec<(String, Vec<Lint>)>,
+    /// Files that couldn't be linted at all, such as ones that couldn't be read.
+    pub failures: Vec<(String, LoxError)>,
+}
+
+impl LintReport {
+    pub fn new() -> LintReport {
+        LintReport::default()
+    }
+
+    pub fn add(&mut self, path: impl Into<String>, lints: Vec<Lint>) {
+        self.files.push((path.into(), lints));
+    }
+
+    pub fn fail(&mut self, path: impl Into<String>, error: LoxError) {
+        self.failures.push((path.into(), error));
+    }
+
+    pub fn lints(&self) -> impl Iterator<Item = (&str, &Lint)> {
+        self.files
+            .iter()
+            .flat_map(|(path, lints)| lints.iter().map(move |lint| (path.as_str(), lint)))
+    }
+
+    /// Whether any finding has error severity or a file couldn't be linted, which fails the run.
+    pub fn has_errors(&self) -> bool {
+        !self.failures.is_empty()
+            || self
+                .lints()
+                .any(|(_, lint)| lint.severity == Severity::Error)
+    }
+
+    /// How many findings each rule had at each severity.
+    pub fn counts(&self) -> BTreeMap<(&'static str, Severity), usize> {
+        let mut counts = BTreeMap::new();
+        for (_, lint) in self.lints() {
+            *counts.entry((lint.rule, lint.severity)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// A line per rule with findings and a total, such as `no-tabs: 2 warnings`.  Files that
+    /// couldn't be linted are counted at the end.
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+        let mut totals = BTreeMap::new();
+        for ((rule, severity), count) in self.counts() {
+            let _ = writeln!(summary, "{}: {}", rule, plural(count, severity));
+            *totals.entry(severity).or_insert(0) += count;
+        }
+        let problems: usize = totals.values().sum();
+        let breakdown: Vec<String> = totals
+            .iter()
+            .rev()
+            .map(|(&severity, &count)| plural(count, severity))
+            .collect();
+        let files = self.files.len();
+        let _ = write!(
+            summary,
+            "{} problem{}",
+            problems,
+            if problems == 1 { "" } else { "s" }
+        );
+        if !breakdown.is_empty() {
+            let _ = write!(summary, " ({})", breakdown.join(", "));
+        }
+        let _ = write!(
+            summary,
+            " in {} file{}",
+            files,
+            if files == 1 { "" } else { "s" }
+        );
+        let failed = self.failures.len();
+        if failed > 0 {
+            let _ = write!(
+                summary,
+                ", {} file{} couldn't be linted",
+                failed,
+                if failed == 1 { "" } else { "s" }
+            );
+        }
+        summary.push('\n');
+        summary
+    }
+
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => self.text(),
+            OutputFormat::Sarif => self.sarif(),
+            OutputFormat::Checkstyle => self.checkstyle(),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for (path, lint) in self.lints() {
+            let _ = writeln!(text, "{}: {}", path, lint);
+        }
+        for (path, error) in &self.failures {
+            let _ = writeln!(text, "{}: error: {}", path, error);
+        }
+        text
+    }
+
+    pub fn sarif(&self) -> String {
+        let mut rules: Vec<&str> = self.lints().map(|(_, lint)| lint.rule).collect();
+        rules.sort_unstable();
+        rules.dedup();
+
+        let results: Vec<Value> = self
+            .lints()
+            .map(|(path, lint)| {
+                let (line, column) = lint.error.span();
+                json!({
+                    "ruleId": lint.rule,
+                    "ruleIndex": rules.binary_search(&lint.rule).unwrap_or_default(),
+                    "level": lint.severity.to_string(),
+                    "message": { "text": message(&lint.error) },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": artifact_location(path),
+                            "region": { "startLine": line, "startColumn": column },
+                        }
+                    }],
+                })
+            })
+            .collect();
+        let notifications: Vec<Value> = self
+            .failures
+            .iter()
+            .map(|(path, error)| {
+                json!({
+                    "level": "error",
+                    "message": { "text": error.to_string() },
+                    "locations": [{ "physicalLocation": { "artifactLocation": artifact_location(path) } }],
+                })
+            })
+            .collect();
+
+        let log = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                    }
+                },
+                "invocations": [{
+                    "executionSuccessful": notifications.is_empty(),
+                    "toolExecutionNotifications": notifications,
+                }],
+                "results": results,
+            }],
+        });
+        let mut sarif = serde_json::to_string_pretty(&log).unwrap_or_default();
+        sarif.push('\n');
+        sarif
+    }
+
+    /// Every linted file gets a `<file>` element, even one without findings, so tools can tell it
+    /// was checked.  A file that couldn't be linted gets an error without a position.
+    pub fn checkstyle(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(xml, "<checkstyle version=\"{}\">", CHECKSTYLE_VERSION);
+        for (path, lints) in &self.files {
+            let _ = writeln!(xml, "  <file name=\"{}\">", escape_xml(path));
+            for lint in lints {
+                let (line, column) = lint.error.span();
+                let _ = writeln!(
+                    xml,
+                    "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}.{}\"/>",
+                    line,
+                    column,
+                    lint.severity,
+                    escape_xml(&message(&lint.error)),
+                    env!("CARGO_PKG_NAME"),
+                    lint.rule
+                );
+            }
+            xml.push_str("  </file>\n");
+        }
+        for (path, error) in &self.failures {
+            let _ = writeln!(xml, "  <file name=\"{}\">", escape_xml(path));
+            let _ = writeln!(
+                xml,
+                "    <error severity=\"error\" message=\"{}\" source=\"{}\"/>",
+                escape_xml(&error.to_string()),
+                env!("CARGO_PKG_NAME")
+            );
+            xml.push_str("  </file>\n");
+        }
+        xml.push_str("</checkstyle>\n");
+        xml
+    }
+}
+
+fn plural(count: usize, severity: Severity) -> String {
+    format!(
+        "{} {}{}",
+        count,
+        severity,
+        if count == 1 { "" } else { "s" }
+    )
+}
+
+/// The error's message without the `Linter Error line:column` it starts with, since the formats
+/// carry the position separately.
+fn message(error: &LinterError) -> String {
+    let (line, column) = error.span();
+    let text = error.to_string();
+    let prefix = format!("Linter Error {}:{} ", line, column);
+    match text.strip_prefix(&prefix) {
+        Some(message) => message.to_string(),
+        None => text,
+    }
+}
+
+/// The path as a SARIF artifact location: a relative reference against [SARIF_SRCROOT], or a
+/// `file` URI for an absolute path.  Segments are percent-encoded and separated by `/` whatever
+/// the platform's separator is.
+fn artifact_location(path: &str) -> Value {
+    let mut segments = Vec::new();
+    let mut absolute = false;
+    for component in Path::new(path).components() {
+        match component {
+            // A drive letter's colon means the same in a URI.
+            Component::Prefix(prefix) => {
+                absolute = true;
+                segments.push(
+                    percent_encode(&prefix.as_os_str().to_string_lossy()).replace("%3A", ":"),
+                );
+            }
+            Component::RootDir => absolute = true,
+            Component::CurDir => {}
+            Component::ParentDir => segments.push("..".to_string()),
+            Component::Normal(name) => segments.push(percent_encode(&name.to_string_lossy())),
+        }
+    }
+    let uri = segments.join("/");
+    if absolute {
+        json!({ "uri": format!("file:///{}", uri) })
+    } else {
+        json!({ "uri": uri, "uriBaseId": SARIF_SRCROOT })
+    }
+}
+
+/// Every byte of the text except the unreserved characters of RFC 3986 as `%XX`.
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => {
+                let _ = write!(encoded, "%{:02X}", byte);
+            }
+        }
+    }
+    encoded
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\t' => escaped.push_str("&#9;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
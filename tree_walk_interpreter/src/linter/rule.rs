@@ -0,0 +1,96 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Deserialize;
+
+use crate::error::LinterError;
+use crate::linter::config::LintConfig;
+use crate::linter::fix::Fix;
+use crate::linter::semantic::ScopeAnalysis;
+use crate::parser::ast::Stmt;
+use crate::scanner::tokens::LoxToken;
+
+/// How seriously a rule's findings are taken, set per rule in the [LintConfig].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The rule isn't run at all.
+    Off,
+    Warn,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Off => write!(f, "off"),
+            Severity::Warn => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// What a rule gets to look at.
+#[derive(Clone, Copy, Debug)]
+pub struct LintContext<'a> {
+    /// Every token of the source including whitespace and comments, but not the ones that failed
+    /// to scan or the final `Eof`.
+    pub tokens: &'a [LoxToken],
+    /// The parsed program, `None` if it has syntax errors.  Only the rules built into the linter
+    /// look at it, the syntax tree isn't part of the public API.
+    pub(crate) statements: Option<&'a [Stmt]>,
+    /// The locals of [statements](Self::statements), `None` along with them.
+    pub(crate) scopes: Option<&'a ScopeAnalysis<'a>>,
+    /// Position just after the last token.
+    pub end: (usize, usize),
+    pub config: &'a LintConfig,
+}
+
+impl<'a> LintContext<'a> {
+    /// Index of the token at the position.
+    pub fn token_at(&self, line: usize, column: usize) -> Option<usize> {
+        self.tokens
+            .iter()
+            .position(|t| t.line == line && t.column == column)
+    }
+
+    /// Position of the token at `index`, or the end of the source past the last one.
+    pub fn position(&self, index: usize) -> (usize, usize) {
+        self.tokens
+            .get(index)
+            .map_or(self.end, |t| (t.line, t.column))
+    }
+}
+
+/// A single check the linter can run, identified in configs and `lint-disable-next-line`
+/// comments by its [id](LintRule::id).  Rules are shared by the threads linting files in
+/// parallel.
+pub trait LintRule: Send + Sync {
+    /// Kebab case name of the rule, such as `no-tabs`.
+    fn id(&self) -> &'static str;
+
+    /// Severity used when the config doesn't set one.
+    fn default_severity(&self) -> Severity;
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError>;
+
+    /// An edit that fixes one of this rule's findings, for rules whose fixes are safe to apply
+    /// without looking.
+    fn fix(&self, _error: &LinterError, _context: &LintContext) -> Option<Fix> {
+        None
+    }
+}
+
+/// A finding of one of the rules.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lint {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub error: LinterError,
+    pub fix: Option<Fix>,
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} [{}]", self.severity, self.error, self.rule)
+    }
+}
@@ -0,0 +1,520 @@
+//! Rules that look at the parsed program, they are skipped for sources that don't parse.
+
+use crate::error::LinterError;
+use crate::linter::rule::{LintContext, LintRule, Severity};
+use crate::parser::ast::{Expr, FunctionDeclaration, Literal, Stmt};
+use crate::scanner::tokens::{LoxToken, TokenType};
+
+/// Local variables declared with `var` that are never read.  Names starting with `_` are allowed
+/// to go unused.
+pub struct UnusedVariable;
+
+impl LintRule for UnusedVariable {
+    fn id(&self) -> &'static str {
+        "unused-variable"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        let scopes = match context.scopes {
+            Some(scopes) => scopes,
+            None => return Vec::new(),
+        };
+        scopes
+            .unused
+            .iter()
+            .filter(|local| local.kind == LocalKind::Variable)
+            .map(|local| {
+                let name = local.name;
+                LinterError::UnusedVariable(name.line, name.column, name.lexeme.clone())
+            })
+            .collect()
+    }
+}
+
+/// Function and method parameters that are never read, unless their name starts with `_`.
+pub struct UnusedParameter;
+
+impl LintRule for UnusedParameter {
+    fn id(&self) -> &'static str {
+        "unused-parameter"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        let scopes = match context.scopes {
+            Some(scopes) => scopes,
+            None => return Vec::new(),
+        };
+        scopes
+            .unused
+            .iter()
+            .filter(|local| local.kind == LocalKind::Parameter)
+            .map(|local| {
+                let name = local.name;
+                LinterError::UnusedParameter(name.line, name.column, name.lexeme.clone())
+            })
+            .collect()
+    }
+}
+
+/// Locals, parameters, functions and classes with the same name as a local of an enclosing scope.
+/// Globals can be shadowed freely.
+pub struct Shadowing;
+
+impl LintRule for Shadowing {
+    fn id(&self) -> &'static str {
+        "shadowing"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        let scopes = match context.scopes {
+            Some(scopes) => scopes,
+            None => return Vec::new(),
+        };
+        scopes
+            .shadowed
+            .iter()
+            .map(|(name, outer)| {
+                LinterError::ShadowedVariable(
+                    name.line,
+                    name.column,
+                    name.lexeme.clone(),
+                    outer.line,
+                    outer.column,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Statements following a `return` (or an `if` that returns from both branches) in the same
+/// block, reported once per block at the `return`.
+pub struct UnreachableCode;
+
+impl LintRule for UnreachableCode {
+    fn id(&self) -> &'static str {
+        "unreachable-code"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        let mut errors = Vec::new();
+        if let Some(statements) = context.statements {
+            for_each_statement_list(statements, &mut |statements| {
+                // A return in the last statement has nothing after it.
+                let before_last = statements.split_last().map_or(&[][..], |(_, rest)| rest);
+                if let Some(keyword) = before_last.iter().find_map(always_returns) {
+                    errors.push(LinterError::UnreachableCode(keyword.line, keyword.column));
+                }
+            });
+        }
+        errors
+    }
+}
+
+/// `a = a` and `a.b = a.b`, which do nothing.
+pub struct SelfAssignment;
+
+impl LintRule for SelfAssignment {
+    fn id(&self) -> &'static str {
+        "self-assignment"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        let mut errors = Vec::new();
+        if let Some(statements) = context.statements {
+            for_each_expression(statements, &mut |expr| {
+                let (name, target, value) = match expr {
+                    Expr::Assign { name, value, .. } => (name, Some(name.lexeme.clone()), value),
+                    Expr::Set {
+                        object,
+                        name,
+                        value,
+                    } => (
+                        name,
+                        place(object).map(|object| format!("{}.{}", object, name.lexeme)),
+                        value,
+                    ),
+                    _ => return,
+                };
+                if let Some(target) = target.filter(|target| Some(target) == place(value).as_ref())
+                {
+                    errors.push(LinterError::SelfAssignment(name.line, name.column, target));
+                }
+            });
+        }
+        errors
+    }
+}
+
+/// Comparisons between two literals, or of a variable (or property) with itself.  The latter
+/// aren't quite constant, `NaN` is the one value that isn't equal to itself.
+pub struct ConstantComparison;
+
+impl LintRule for ConstantComparison {
+    fn id(&self) -> &'static str {
+        "constant-comparison"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        let mut errors = Vec::new();
+        if let Some(statements) = context.statements {
+            for_each_expression(statements, &mut |expr| {
+                if let Expr::Binary {
+                    left,
+                    operator,
+                    right,
+                } = expr
+                {
+                    if let Some(error) = constant_comparison(left, operator, right) {
+                        errors.push(error);
+                    }
+                }
+            });
+        }
+        errors
+    }
+}
+
+fn constant_comparison(left: &Expr, operator: &LoxToken, right: &Expr) -> Option<LinterError> {
+    let (line, column) = (operator.line, operator.column);
+    if let (Expr::Literal(a), Expr::Literal(b)) = (left, right) {
+        let result = match (&operator.token_type, a, b) {
+            (TokenType::EqualEqual, a, b) => a == b,
+            (TokenType::BangEqual, a, b) => a != b,
+            (TokenType::Less, Literal::Number(a), Literal::Number(b)) => a < b,
+            (TokenType::LessEqual, Literal::Number(a), Literal::Number(b)) => a <= b,
+            (TokenType::Greater, Literal::Number(a), Literal::Number(b)) => a > b,
+            (TokenType::GreaterEqual, Literal::Number(a), Literal::Number(b)) => a >= b,
+            // Anything else is a runtime error, or not a comparison at all.
+            _ => return None,
+        };
+        return Some(LinterError::ConstantComparison(line, column, result));
+    }
+
+    // Only variables are certain to read the same value twice, a property may be a method that
+    // is bound anew on each access or a getter with side effects.
+    let left = variable(left)?;
+    if Some(&left) != variable(right).as_ref() {
+        return None;
+    }
+    let result = match operator.token_type {
+        TokenType::EqualEqual | TokenType::LessEqual | TokenType::GreaterEqual => true,
+        TokenType::BangEqual | TokenType::Less | TokenType::Greater => false,
+        _ => return None,
+    };
+    Some(LinterError::SelfComparison(line, column, left, result))
+}
+
+/// The variable an expression reads, including `this`.
+fn variable(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Variable { name, .. } => Some(name.lexeme.clone()),
+        Expr::This { .. } => Some("this".to_string()),
+        _ => None,
+    }
+}
+
+/// The variable or property an expression reads, written out like `a.b.c`.
+fn place(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Variable { name, .. } => Some(name.lexeme.clone()),
+        Expr::This { .. } => Some("this".to_string()),
+        Expr::Get { object, name } => Some(format!("{}.{}", place(object)?, name.lexeme)),
+        _ => None,
+    }
+}
+
+/// The `return` a statement always ends up at, if every path through it returns.
+fn always_returns(statement: &Stmt) -> Option<&LoxToken> {
+    match statement {
+        Stmt::Return { keyword, .. } => Some(keyword),
+        Stmt::Block(statements) => statements.iter().find_map(always_returns),
+        Stmt::If {
+            then_branch,
+            else_branch: Some(else_branch),
+            ..
+        } => {
+            always_returns(else_branch)?;
+            always_returns(then_branch)
+        }
+        _ => None,
+    }
+}
+
+/// Calls `visit` with the top level statements, and the statements of every block and function
+/// body in them.
+fn for_each_statement_list<'a>(statements: &'a [Stmt], visit: &mut impl FnMut(&'a [Stmt])) {
+    visit(statements);
+    for statement in statements {
+        match statement {
+            Stmt::Block(statements) => for_each_statement_list(statements, visit),
+            Stmt::Class(declaration) => {
+                for method in &declaration.methods {
+                    for_each_statement_list(&method.body, visit);
+                }
+            }
+            Stmt::Function(declaration) => for_each_statement_list(&declaration.body, visit),
+            Stmt::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                for_each_statement_list(std::slice::from_ref(then_branch), visit);
+                if let Some(else_branch) = else_branch {
+                    for_each_statement_list(std::slice::from_ref(else_branch), visit);
+                }
+            }
+            Stmt::While { body, .. } => for_each_statement_list(std::slice::from_ref(body), visit),
+            Stmt::Expression(_) | Stmt::Print(_) | Stmt::Return { .. } | Stmt::Var { .. } => {}
+        }
+    }
+}
+
+/// Calls `visit` with every expression in the statements, outer expressions before the ones
+/// nested in them.
+fn for_each_expression<'a>(statements: &'a [Stmt], visit: &mut impl FnMut(&'a Expr)) {
+    for statement in statements {
+        match statement {
+            Stmt::Block(statements) => for_each_expression(statements, visit),
+            Stmt::Class(declaration) => {
+                if let Some(superclass) = &declaration.superclass {
+                    walk_expression(superclass, visit);
+                }
+                for method in &declaration.methods {
+                    for_each_expression(&method.body, visit);
+                }
+            }
+            Stmt::Expression(expr) | Stmt::Print(expr) => walk_expression(expr, visit),
+            Stmt::Function(declaration) => for_each_expression(&declaration.body, visit),
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                walk_expression(condition, visit);
+                for_each_expression(std::slice::from_ref(then_branch), visit);
+                if let Some(else_branch) = else_branch {
+                    for_each_expression(std::slice::from_ref(else_branch), visit);
+                }
+            }
+            Stmt::Return { value, .. } => {
+                if let Some(value) = value {
+                    walk_expression(value, visit);
+                }
+            }
+            Stmt::Var { initializer, .. } => {
+                if let Some(initializer) = initializer {
+                    walk_expression(initializer, visit);
+                }
+            }
+            Stmt::While {
+                condition, body, ..
+            } => {
+                walk_expression(condition, visit);
+                for_each_expression(std::slice::from_ref(body), visit);
+            }
+        }
+    }
+}
+
+fn walk_expression<'a>(expr: &'a Expr, visit: &mut impl FnMut(&'a Expr)) {
+    visit(expr);
+    match expr {
+        Expr::Assign { value, .. } => walk_expression(value, visit),
+        Expr::Binary { left, right, .. } | Expr::Logical { left, right, .. } => {
+            walk_expression(left, visit);
+            walk_expression(right, visit);
+        }
+        Expr::Call {
+            callee, arguments, ..
+        } => {
+            walk_expression(callee, visit);
+            for argument in arguments {
+                walk_expression(argument, visit);
+            }
+        }
+        Expr::Get { object, .. } => walk_expression(object, visit),
+        Expr::Grouping(expr) => walk_expression(expr, visit),
+        Expr::Set { object, value, .. } => {
+            walk_expression(object, visit);
+            walk_expression(value, visit);
+        }
+        Expr::Unary { right, .. } => walk_expression(right, visit),
+        Expr::Literal(_) | Expr::Super { .. } | Expr::This { .. } | Expr::Variable { .. } => {}
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum LocalKind {
+    Variable,
+    Parameter,
+    /// A function or class declared in a block, which is never reported as unused.
+    Declaration,
+}
+
+#[derive(Debug)]
+struct Local<'a> {
+    name: &'a LoxToken,
+    kind: LocalKind,
+    used: bool,
+}
+
+/// Tracks the local scopes of a program the same way the resolver does, recording which locals
+/// are never read and which shadow another local.  Worked out once per source and shared by the
+/// rules through the [LintContext].
+#[derive(Debug, Default)]
+pub(crate) struct ScopeAnalysis<'a> {
+    scopes: Vec<Vec<Local<'a>>>,
+    unused: Vec<Local<'a>>,
+    /// Each shadowing declaration, along with the declaration it shadows.
+    shadowed: Vec<(&'a LoxToken, &'a LoxToken)>,
+}
+
+impl<'a> ScopeAnalysis<'a> {
+    pub(crate) fn of(statements: &'a [Stmt]) -> ScopeAnalysis<'a> {
+        let mut analysis = ScopeAnalysis::default();
+        analysis.statements(statements);
+        analysis
+    }
+
+    fn statements(&mut self, statements: &'a [Stmt]) {
+        for statement in statements {
+            self.statement(statement);
+        }
+    }
+
+    fn statement(&mut self, statement: &'a Stmt) {
+        match statement {
+            Stmt::Block(statements) => {
+                self.scopes.push(Vec::new());
+                self.statements(statements);
+                self.end_scope();
+            }
+            Stmt::Class(declaration) => {
+                self.declare(&declaration.name, LocalKind::Declaration);
+                if let Some(superclass) = &declaration.superclass {
+                    self.expression(superclass);
+                }
+                for method in &declaration.methods {
+                    self.function(method);
+                }
+            }
+            Stmt::Expression(expr) | Stmt::Print(expr) => self.expression(expr),
+            Stmt::Function(declaration) => {
+                // Declared before the body, which can call the function recursively.
+                self.declare(&declaration.name, LocalKind::Declaration);
+                self.function(declaration);
+            }
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.expression(condition);
+                self.statement(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.statement(else_branch);
+                }
+            }
+            Stmt::Return { value, .. } => {
+                if let Some(value) = value {
+                    self.expression(value);
+                }
+            }
+            Stmt::Var { name, initializer } => {
+                if let Some(initializer) = initializer {
+                    self.expression(initializer);
+                }
+                self.declare(name, LocalKind::Variable);
+            }
+            Stmt::While {
+                condition, body, ..
+            } => {
+                self.expression(condition);
+                self.statement(body);
+            }
+        }
+    }
+
+    fn function(&mut self, declaration: &'a FunctionDeclaration) {
+        self.scopes.push(Vec::new());
+        for param in &declaration.params {
+            self.declare(param, LocalKind::Parameter);
+        }
+        self.statements(&declaration.body);
+        self.end_scope();
+    }
+
+    fn expression(&mut self, expr: &'a Expr) {
+        walk_expression(expr, &mut |expr| {
+            if let Expr::Variable { name, .. } = expr {
+                self.read(&name.lexeme);
+            }
+        });
+    }
+
+    /// Declares a local, globals aren't tracked.
+    fn declare(&mut self, name: &'a LoxToken, kind: LocalKind) {
+        let (current, outer) = match self.scopes.split_last_mut() {
+            Some(split) => split,
+            None => return,
+        };
+        if let Some(shadowed) = outer
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .find(|local| local.name.lexeme == name.lexeme)
+        {
+            self.shadowed.push((name, shadowed.name));
+        }
+        current.push(Local {
+            name,
+            kind,
+            used: false,
+        });
+    }
+
+    fn read(&mut self, name: &str) {
+        if let Some(local) = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .flat_map(|scope| scope.iter_mut().rev())
+            .find(|local| local.name.lexeme == name)
+        {
+            local.used = true;
+        }
+    }
+
+    fn end_scope(&mut self) {
+        let scope = self.scopes.pop().unwrap_or_default();
+        self.unused.extend(
+            scope
+                .into_iter()
+                .filter(|local| !local.used && !local.name.lexeme.starts_with('_')),
+        );
+    }
+}
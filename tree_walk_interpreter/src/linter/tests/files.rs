@@ -0,0 +1,147 @@
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::{Path, PathBuf};
+
+use crate::error::LoxError;
+use crate::linter::{lox_files, LintConfig, LintRegistry, Linter, Severity, IGNORE_FILE};
+
+/// A directory tree of scripts, removed again when dropped.
+struct Tree(PathBuf);
+
+impl Tree {
+    fn new(name: &str, files: &[(&str, &str)]) -> Tree {
+        let root = std::env::temp_dir().join(format!("darmok_lox_{}_{}", name, std::process::id()));
+        let _ = remove_dir_all(&root);
+        for (path, contents) in files {
+            let path = root.join(path);
+            create_dir_all(path.parent().unwrap()).unwrap();
+            write(path, contents).unwrap();
+        }
+        Tree(root)
+    }
+
+    fn relative(&self, paths: Vec<PathBuf>) -> Vec<String> {
+        paths
+            .iter()
+            .map(|p| p.strip_prefix(&self.0).unwrap().display().to_string())
+            .collect()
+    }
+}
+
+impl Drop for Tree {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn finding_scripts() {
+    let tree = Tree::new(
+        "lint_walk",
+        &[
+            ("b.lox", ""),
+            ("a.lox", ""),
+            ("notes.txt", ""),
+            ("nested/c.lox", ""),
+            ("vendor/d.lox", ""),
+            ("nested/generated.lox", ""),
+            (IGNORE_FILE, "vendor/\n"),
+            ("nested/.loxlintignore", "generated.lox\n"),
+        ],
+    );
+    assert_eq!(
+        tree.relative(lox_files(&[&tree.0]).0),
+        ["a.lox", "b.lox", "nested/c.lox"]
+    );
+}
+
+#[test]
+fn files_are_always_linted() {
+    let tree = Tree::new(
+        "lint_explicit",
+        &[
+            ("script.txt", ""),
+            ("vendor/d.lox", ""),
+            (IGNORE_FILE, "vendor/\n"),
+        ],
+    );
+    let paths = [tree.0.join("script.txt"), tree.0.join("vendor/d.lox")];
+    assert_eq!(
+        tree.relative(lox_files(&paths).0),
+        ["script.txt", "vendor/d.lox"]
+    );
+}
+
+#[test]
+fn missing_path() {
+    let tree = Tree::new("lint_missing", &[("a.lox", "")]);
+    let missing = Path::new("no/such/directory");
+    let (files, failures) = lox_files(&[missing, &tree.0]);
+
+    // The other paths are still searched.
+    assert_eq!(tree.relative(files), ["a.lox"]);
+    assert_eq!(failures.len(), 1);
+    assert!(matches!(&failures[0], (path, LoxError::IoError(..)) if path == "no/such/directory"));
+}
+
+#[test]
+fn linting_files_in_parallel() {
+    let scripts: Vec<(String, String)> = (0..20)
+        .map(|i| (format!("{:02}.lox", i), "print  1;\n".repeat(i % 3)))
+        .collect();
+    let files: Vec<(&str, &str)> = scripts
+        .iter()
+        .map(|(name, source)| (name.as_str(), source.as_str()))
+        .collect();
+    let tree = Tree::new("lint_parallel", &files);
+
+    let mut config = LintConfig::default();
+    config
+        .rules
+        .insert("double-space".to_string(), Severity::Error);
+    let linter = Linter::new(LintRegistry::standard(), config).unwrap();
+    let report = linter.lint_files(&lox_files(&[&tree.0]).0);
+
+    // Files are reported in order however the work was split up.
+    let counts: Vec<usize> = report.files.iter().map(|(_, lints)| lints.len()).collect();
+    let expected: Vec<usize> = (0..20).map(|i| i % 3).collect();
+    assert_eq!(counts, expected);
+    assert!(report.has_errors());
+}
+
+#[test]
+fn unreadable_files_are_failures() {
+    let tree = Tree::new(
+        "lint_unreadable",
+        &[("a.lox", "print  1;\n"), ("c.lox", "")],
+    );
+    let files = vec![
+        tree.0.join("a.lox"),
+        tree.0.join("b.lox"),
+        tree.0.join("c.lox"),
+    ];
+    let report = Linter::default().lint_files(&files);
+
+    assert_eq!(
+        tree.relative(report.files.iter().map(|(p, _)| p.into()).collect()),
+        ["a.lox", "c.lox"]
+    );
+    assert_eq!(report.files[0].1.len(), 1);
+    assert_eq!(report.failures.len(), 1);
+    assert!(
+        matches!(&report.failures[0], (path, LoxError::IoError(..)) if path.ends_with("b.lox"))
+    );
+    assert!(report.has_errors());
+}
+
+#[test]
+fn fixing_files() {
+    let tree = Tree::new(
+        "lint_fix",
+        &[("a.lox", "print  1;\n"), ("b.lox", "print 1;\n")],
+    );
+    let (files, _) = lox_files(&[&tree.0]);
+    let linter = Linter::default();
+    assert_eq!(linter.fix_files(&files), [Ok(1), Ok(0)]);
+    assert_eq!(std::fs::read_to_string(&files[0]).unwrap(), "print 1;\n");
+    assert!(linter.lint_files(&files).lints().next().is_none());
+}
@@ -0,0 +1,57 @@
+use crate::linter::{apply_fixes, Fix, Linter};
+
+#[test]
+fn applying_fixes() {
+    let fixes = [
+        Fix {
+            start: (1, 6),
+            end: (1, 8),
+            replacement: " ".to_string(),
+        },
+        // Overlaps the fix before it, so it's skipped.
+        Fix {
+            start: (1, 7),
+            end: (1, 8),
+            replacement: "!".to_string(),
+        },
+        Fix {
+            start: (2, 1),
+            end: (2, 2),
+            replacement: "b".to_string(),
+        },
+    ];
+    assert_eq!(
+        apply_fixes("print  a;\na;", &fixes),
+        ("print a;\nb;".to_string(), 2)
+    );
+    // Positions that aren't at a token are skipped.
+    let inside_token = Fix {
+        start: (1, 2),
+        end: (1, 3),
+        replacement: "x".to_string(),
+    };
+    assert_eq!(
+        apply_fixes("print a;", [&inside_token]),
+        ("print a;".to_string(), 0)
+    );
+    assert_eq!(apply_fixes("print @;", &fixes), ("print @;".to_string(), 0));
+}
+
+#[test]
+fn whitespace_fixes() {
+    let linter = Linter::default();
+    assert_eq!(
+        linter.fix("{\n\tprint  \"a\"; \t\r\n  \r\n}"),
+        ("{\n    print \"a\";\n\n}".to_string(), 6)
+    );
+    assert_eq!(
+        linter.fix("print 1;\r\nprint 2;\nprint 3;  // c\r\n"),
+        ("print 1;\r\nprint 2;\r\nprint 3; // c\r\n".to_string(), 2)
+    );
+}
+
+#[test]
+fn suppressed_findings_are_not_fixed() {
+    let source = "// lint-disable-next-line double-space\nprint  1;\n";
+    assert_eq!(Linter::default().fix(source), (source.to_string(), 0));
+}
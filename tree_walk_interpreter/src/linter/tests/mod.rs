@@ -1,13 +1,78 @@
-use crate::linter::lint_tokens;
-use crate::scanner::scan_with_whitespace;
-use crate::LinterError;
-
-#[test]
-fn double_space() {
-    let results = scan_with_whitespace("print  \"test\"", false);
-    let lint_results = lint_tokens(&results);
-    assert_eq!(
-        lint_results,
-        vec![LinterError::DoubleSpaceDetected(1, 6).into()]
-    )
-}
+use crate::error::{LinterError, LoxError};
+use crate::linter::{lint_tokens, LintConfig, LintRegistry, Linter};
+use crate::scanner::scan_with_whitespace;
+
+mod files;
+mod fix;
+mod naming;
+mod report;
+mod rules;
+mod semantic;
+
+fn lints(source: &str) -> Vec<LoxError> {
+    lint_tokens(&scan_with_whitespace(source, false))
+}
+
+#[test]
+fn double_space() {
+    let results = scan_with_whitespace("print  \"test\"", false);
+    let lint_results = lint_tokens(&results);
+    assert_eq!(
+        lint_results,
+        vec![LinterError::DoubleSpaceDetected(1, 6).into()]
+    )
+}
+
+#[test]
+fn double_space_only_mid_line() {
+    assert_eq!(lints("{\n    print 1;\n}\n"), vec![]);
+    assert_eq!(
+        lints("var a =   1;\n"),
+        vec![LinterError::DoubleSpaceDetected(1, 8).into()]
+    );
+}
+
+#[test]
+fn tabs() {
+    assert_eq!(
+        lints("{\n\tprint 1;\n}\n"),
+        vec![LinterError::TabDetected(2, 1).into()]
+    );
+}
+
+#[test]
+fn line_endings() {
+    assert_eq!(lints("print 1;\r\nprint 2;\r\n"), vec![]);
+    assert_eq!(lints("print 1;\r\n// one\r\nprint 2; // two\r\n"), vec![]);
+    assert_eq!(
+        lints("print 1;\nprint 2;\r\nprint 3;\n"),
+        vec![LinterError::InconsistentLineEnding(2, 9, "\\r\\n", "\\n").into()]
+    );
+}
+
+#[test]
+fn trailing_whitespace() {
+    assert_eq!(
+        lints("print 1; \n  \nprint 2;\t"),
+        vec![
+            LinterError::TrailingWhitespace(1, 9).into(),
+            LinterError::TrailingWhitespace(2, 1).into(),
+            LinterError::TabDetected(3, 9).into(),
+            LinterError::TrailingWhitespace(3, 9).into(),
+        ]
+    );
+}
+
+#[test]
+fn indentation_width() {
+    assert_eq!(
+        lints("{\n   print 1;\n}\n"),
+        vec![LinterError::IndentationWidth(2, 4, 3, 4).into()]
+    );
+    let two_spaces = LintConfig {
+        indent_width: 2,
+        ..LintConfig::default()
+    };
+    let linter = Linter::new(LintRegistry::standard(), two_spaces).unwrap();
+    assert_eq!(linter.lint("{\n  print 1;\n}\n"), vec![]);
+}
@@ -0,0 +1,110 @@
+use crate::error::LinterError;
+use crate::linter::{Case, LintConfig, LintRegistry, Linter};
+
+fn naming_lints(linter: &Linter, source: &str) -> Vec<LinterError> {
+    linter
+        .lint(source)
+        .into_iter()
+        .filter(|lint| lint.rule.ends_with("-naming"))
+        .map(|lint| lint.error)
+        .collect()
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(Case::UpperCamelCase.convert("http_server"), "HttpServer");
+    assert_eq!(Case::LowerCamelCase.convert("HTTPServer"), "httpServer");
+    assert_eq!(
+        Case::LowerCamelCase.convert("_Unused_Thing"),
+        "_unusedThing"
+    );
+    assert_eq!(
+        Case::SnakeCase.convert("getHTTPResponse2"),
+        "get_http_response2"
+    );
+    assert_eq!(Case::ScreamingSnakeCase.convert("maxValue"), "MAX_VALUE");
+
+    assert!(Case::UpperCamelCase.matches("Point3D"));
+    assert!(!Case::UpperCamelCase.matches("point"));
+    assert!(Case::LowerCamelCase.matches("x"));
+    assert!(Case::LowerCamelCase.matches("_unused"));
+    assert!(!Case::LowerCamelCase.matches("snake_case"));
+    assert!(Case::SnakeCase.matches("snake_case"));
+    assert!(Case::ScreamingSnakeCase.matches("MAX_2"));
+}
+
+#[test]
+fn default_conventions() {
+    let source =
+        "class point {}\nfun Make_Point() {}\nvar some_point = point();\nclass Fine < point {}\n";
+    assert_eq!(
+        naming_lints(&Linter::default(), source),
+        vec![
+            LinterError::NamingConvention(
+                1,
+                7,
+                "Class",
+                "point".to_string(),
+                Case::UpperCamelCase,
+                "Point".to_string()
+            ),
+            LinterError::NamingConvention(
+                2,
+                5,
+                "Function",
+                "Make_Point".to_string(),
+                Case::LowerCamelCase,
+                "makePoint".to_string()
+            ),
+            LinterError::NamingConvention(
+                3,
+                5,
+                "Variable",
+                "some_point".to_string(),
+                Case::LowerCamelCase,
+                "somePoint".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn configured_conventions() {
+    let config = LintConfig::parse(
+        LintConfig::FILE_NAME,
+        "[naming]\nfunctions = \"snake_case\"\nvariables = \"SCREAMING_SNAKE_CASE\"",
+    )
+    .unwrap();
+    let linter = Linter::new(LintRegistry::standard(), config).unwrap();
+    assert_eq!(
+        naming_lints(&linter, "fun make_point() {}\nvar maxPoints = 1;\n"),
+        vec![LinterError::NamingConvention(
+            2,
+            5,
+            "Variable",
+            "maxPoints".to_string(),
+            Case::ScreamingSnakeCase,
+            "MAX_POINTS".to_string()
+        )]
+    );
+
+    assert!(
+        LintConfig::parse(LintConfig::FILE_NAME, "[naming]\nclasses = \"kebab-case\"").is_err()
+    );
+}
+
+#[test]
+fn message() {
+    let error = LinterError::NamingConvention(
+        1,
+        5,
+        "Variable",
+        "some_point".to_string(),
+        Case::LowerCamelCase,
+        "somePoint".to_string(),
+    );
+    assert_eq!(
+        error.to_string(),
+        "Linter Error 1:5 Variable 'some_point' should be lowerCamelCase, such as 'somePoint'"
+    );
+}
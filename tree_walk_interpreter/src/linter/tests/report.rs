@@ -0,0 +1,172 @@
+use serde_json::Value;
+
+use crate::error::LoxError;
+use crate::linter::{LintConfig, LintRegistry, LintReport, Linter, OutputFormat, Severity};
+
+fn report() -> LintReport {
+    let linter = Linter::default();
+    let mut report = LintReport::new();
+    report.add("a.lox", linter.lint("print  \"<&>\";\n"));
+    report.add("dir/b.lox", linter.lint("var Foo = 1;\nprint Foo;\n"));
+    report.add("clean.lox", Vec::new());
+    report
+}
+
+#[test]
+fn text_report() {
+    assert_eq!(
+        report().render(OutputFormat::Text),
+        "a.lox: warning: Linter Error 1:6 More than one space detected, standard style is to only have one space [double-space]\n\
+         dir/b.lox: warning: Linter Error 1:5 Variable 'Foo' should be lowerCamelCase, such as 'foo' [variable-naming]\n"
+    );
+}
+
+#[test]
+fn sarif_report() {
+    let sarif: Value = serde_json::from_str(&report().render(OutputFormat::Sarif)).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "darmok_lox");
+    assert_eq!(
+        run["tool"]["driver"]["rules"],
+        serde_json::json!([{ "id": "double-space" }, { "id": "variable-naming" }])
+    );
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    let result = &results[1];
+    assert_eq!(result["ruleId"], "variable-naming");
+    assert_eq!(result["ruleIndex"], 1);
+    assert_eq!(result["level"], "warning");
+    assert_eq!(
+        result["message"]["text"],
+        "Variable 'Foo' should be lowerCamelCase, such as 'foo'"
+    );
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(
+        location["artifactLocation"],
+        serde_json::json!({ "uri": "dir/b.lox", "uriBaseId": "%SRCROOT%" })
+    );
+    assert_eq!(location["region"]["startLine"], 1);
+    assert_eq!(location["region"]["startColumn"], 5);
+}
+
+#[test]
+fn sarif_uris() {
+    let linter = Linter::default();
+    let mut report = LintReport::new();
+    report.add("./my scripts/ü#1.lox", linter.lint("print  1;\n"));
+    report.add("/tmp/a b.lox", linter.lint("print  1;\n"));
+    let sarif: Value = serde_json::from_str(&report.sarif()).unwrap();
+    let uris: Vec<&Value> = sarif["runs"][0]["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| &result["locations"][0]["physicalLocation"]["artifactLocation"])
+        .collect();
+    assert_eq!(
+        uris,
+        vec![
+            &serde_json::json!({ "uri": "my%20scripts/%C3%BC%231.lox", "uriBaseId": "%SRCROOT%" }),
+            &serde_json::json!({ "uri": "file:///tmp/a%20b.lox" }),
+        ]
+    );
+}
+
+#[test]
+fn empty_sarif_report() {
+    let sarif: Value = serde_json::from_str(&LintReport::new().sarif()).unwrap();
+    assert_eq!(sarif["runs"][0]["results"], serde_json::json!([]));
+}
+
+#[test]
+fn checkstyle_report() {
+    assert_eq!(
+        report().render(OutputFormat::Checkstyle),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <checkstyle version=\"4.3\">\n  \
+         <file name=\"a.lox\">\n    \
+         <error line=\"1\" column=\"6\" severity=\"warning\" message=\"More than one space detected, standard style is to only have one space\" source=\"darmok_lox.double-space\"/>\n  \
+         </file>\n  \
+         <file name=\"dir/b.lox\">\n    \
+         <error line=\"1\" column=\"5\" severity=\"warning\" message=\"Variable &apos;Foo&apos; should be lowerCamelCase, such as &apos;foo&apos;\" source=\"darmok_lox.variable-naming\"/>\n  \
+         </file>\n  \
+         <file name=\"clean.lox\">\n  \
+         </file>\n\
+         </checkstyle>\n"
+    );
+}
+
+#[test]
+fn output_format_names() {
+    assert_eq!("sarif".parse(), Ok(OutputFormat::Sarif));
+    assert_eq!("checkstyle".parse(), Ok(OutputFormat::Checkstyle));
+    assert!("json".parse::<OutputFormat>().is_err());
+}
+
+#[test]
+fn summary() {
+    let mut config = LintConfig::default();
+    config.rules.insert("no-tabs".to_string(), Severity::Error);
+    let linter = Linter::new(LintRegistry::standard(), config).unwrap();
+    let mut report = LintReport::new();
+    report.add("a.lox", linter.lint("\tprint  1;\n\tprint  2;\n"));
+    report.add("b.lox", linter.lint("var A = 1;\nprint A;\n"));
+
+    assert_eq!(report.counts()[&("no-tabs", Severity::Error)], 2);
+    assert!(report.has_errors());
+    assert_eq!(
+        report.summary(),
+        "double-space: 2 warnings\n\
+         no-tabs: 2 errors\n\
+         variable-naming: 1 warning\n\
+         5 problems (2 errors, 3 warnings) in 2 files\n"
+    );
+}
+
+#[test]
+fn clean_summary() {
+    let mut report = LintReport::new();
+    report.add("a.lox", Vec::new());
+    assert!(!report.has_errors());
+    assert_eq!(report.summary(), "0 problems in 1 file\n");
+}
+
+#[test]
+fn failures() {
+    let mut report = LintReport::new();
+    report.add("a.lox", Vec::new());
+    report.fail(
+        "b.lox",
+        LoxError::IoError("b.lox".to_string(), "permission denied".to_string()),
+    );
+    assert!(report.has_errors());
+    assert_eq!(
+        report.text(),
+        "b.lox: error: Unable to read b.lox: permission denied\n"
+    );
+    assert_eq!(
+        report.summary(),
+        "0 problems in 1 file, 1 file couldn't be linted\n"
+    );
+
+    let sarif: Value = serde_json::from_str(&report.sarif()).unwrap();
+    let invocation = &sarif["runs"][0]["invocations"][0];
+    assert_eq!(invocation["executionSuccessful"], false);
+    let notification = &invocation["toolExecutionNotifications"][0];
+    assert_eq!(notification["level"], "error");
+    assert_eq!(
+        notification["message"]["text"],
+        "Unable to read b.lox: permission denied"
+    );
+    assert_eq!(
+        notification["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "b.lox"
+    );
+
+    assert!(report.checkstyle().contains(
+        "  <file name=\"b.lox\">\n    \
+         <error severity=\"error\" message=\"Unable to read b.lox: permission denied\" source=\"darmok_lox\"/>\n  \
+         </file>\n"
+    ));
+}
@@ -0,0 +1,136 @@
+use crate::error::{LinterError, LoxError};
+use crate::linter::{Fix, Lint, LintConfig, LintContext, LintRegistry, LintRule, Linter, Severity};
+use crate::scanner::tokens::TokenType;
+
+fn linter(config: &str) -> Linter {
+    let config = LintConfig::parse(LintConfig::FILE_NAME, config).unwrap();
+    Linter::new(LintRegistry::standard(), config).unwrap()
+}
+
+#[test]
+fn default_severities() {
+    assert_eq!(
+        Linter::default().lint("print\t1;  \n"),
+        vec![
+            Lint {
+                rule: "no-tabs",
+                severity: Severity::Warn,
+                error: LinterError::TabDetected(1, 6),
+                fix: Some(Fix {
+                    start: (1, 6),
+                    end: (1, 7),
+                    replacement: " ".to_string(),
+                }),
+            },
+            Lint {
+                rule: "trailing-whitespace",
+                severity: Severity::Warn,
+                error: LinterError::TrailingWhitespace(1, 9),
+                fix: Some(Fix {
+                    start: (1, 9),
+                    end: (1, 11),
+                    replacement: String::new(),
+                }),
+            },
+        ]
+    );
+}
+
+#[test]
+fn configured_severities() {
+    let linter = linter(
+        r#"
+            [rules]
+            no-tabs = "error"
+            trailing-whitespace = "off"
+        "#,
+    );
+    assert_eq!(
+        linter.lint("print\t1;  \n"),
+        vec![Lint {
+            rule: "no-tabs",
+            severity: Severity::Error,
+            error: LinterError::TabDetected(1, 6),
+            fix: Some(Fix {
+                start: (1, 6),
+                end: (1, 7),
+                replacement: " ".to_string(),
+            }),
+        }]
+    );
+}
+
+#[test]
+fn invalid_configs() {
+    assert!(matches!(
+        LintConfig::parse(LintConfig::FILE_NAME, "[rules]\nno-tabs = \"loud\""),
+        Err(LoxError::InvalidConfig(..))
+    ));
+
+    let config = LintConfig::parse(LintConfig::FILE_NAME, "[rules]\nno-tab = \"off\"").unwrap();
+    assert!(matches!(
+        Linter::new(LintRegistry::standard(), config),
+        Err(LoxError::InvalidConfig(_, message)) if message == "unknown rule 'no-tab'"
+    ));
+}
+
+#[test]
+fn disable_next_line() {
+    let source = "// lint-disable-next-line\nprint\t1; \n\
+                  // lint-disable-next-line no-tabs\nprint\t2; \n\
+                  // lint-disable-next-line double-space, no-tabs\nprint\t3;\n\
+                  print\t4;\n";
+    let errors: Vec<LinterError> = Linter::default()
+        .lint(source)
+        .into_iter()
+        .map(|lint| lint.error)
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            LinterError::TrailingWhitespace(4, 9),
+            LinterError::TabDetected(7, 6),
+        ]
+    );
+}
+
+/// Flags every `print`, to check that rules from outside the linter can be registered.
+struct NoPrint;
+
+impl LintRule for NoPrint {
+    fn id(&self) -> &'static str {
+        "no-print"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        context
+            .tokens
+            .iter()
+            .filter(|t| t.token_type == TokenType::Print)
+            .map(|t| LinterError::TrailingWhitespace(t.line, t.column))
+            .collect()
+    }
+}
+
+#[test]
+fn custom_rules() {
+    let mut registry = LintRegistry::new();
+    registry.register(NoPrint);
+    let linter = Linter::new(registry, LintConfig::default()).unwrap();
+    let lints = linter.lint("print 1;\nvar a;\nprint a;");
+    let rules: Vec<(&str, Severity, (usize, usize))> = lints
+        .iter()
+        .map(|lint| (lint.rule, lint.severity, lint.error.span()))
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            ("no-print", Severity::Error, (1, 1)),
+            ("no-print", Severity::Error, (3, 1)),
+        ]
+    );
+}
@@ -0,0 +1,149 @@
+use crate::error::LinterError;
+use crate::linter::Linter;
+
+/// Findings of the rules that look at the parsed program.
+fn lints(source: &str) -> Vec<LinterError> {
+    Linter::default()
+        .lint(source)
+        .into_iter()
+        .filter(|lint| lint.rule != "indentation-width")
+        .map(|lint| lint.error)
+        .collect()
+}
+
+#[test]
+fn unused_locals() {
+    assert_eq!(
+        lints("var global;\n{\n    var a = 1;\n    var b = 2;\n    var _c;\n    print b;\n}\n"),
+        vec![LinterError::UnusedVariable(3, 9, "a".to_string())]
+    );
+    // Assigning a variable doesn't use it.
+    assert_eq!(
+        lints("{\n    var a;\n    a = 1;\n}\n"),
+        vec![LinterError::UnusedVariable(2, 9, "a".to_string())]
+    );
+    // Calling a local function doesn't use an outer variable of the same name.
+    assert_eq!(
+        lints("{\n    var f;\n    {\n        fun f() {}\n        f();\n    }\n}\n"),
+        vec![
+            LinterError::UnusedVariable(2, 9, "f".to_string()),
+            LinterError::ShadowedVariable(4, 13, "f".to_string(), 2, 9),
+        ]
+    );
+    // Closures use the variables they capture.
+    assert_eq!(
+        lints("fun f() {\n    var a;\n    fun g() {\n        return a;\n    }\n    return g;\n}\n"),
+        vec![]
+    );
+}
+
+#[test]
+fn unused_parameters() {
+    assert_eq!(
+        lints("fun f(a, b, _c) {\n    return b;\n}\nclass A {\n    m(d) {}\n}\n"),
+        vec![
+            LinterError::UnusedParameter(1, 7, "a".to_string()),
+            LinterError::UnusedParameter(5, 7, "d".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn shadowing() {
+    assert_eq!(
+        lints("var a;\n{\n    var a;\n    {\n        var a;\n        print a;\n    }\n    print a;\n}\n"),
+        vec![LinterError::ShadowedVariable(5, 13, "a".to_string(), 3, 9)]
+    );
+    assert_eq!(
+        lints(
+            "{\n    var a;\n    fun f(a) {\n        print a;\n    }\n    print a;\n    f(1);\n}\n"
+        ),
+        vec![LinterError::ShadowedVariable(3, 11, "a".to_string(), 2, 9)]
+    );
+    // Local functions and classes are declared like variables.
+    assert_eq!(
+        lints("{\n    var f;\n    class A {}\n    {\n        fun f() {}\n        class A {}\n    }\n    print f;\n}\n"),
+        vec![
+            LinterError::ShadowedVariable(5, 13, "f".to_string(), 2, 9),
+            LinterError::ShadowedVariable(6, 15, "A".to_string(), 3, 11),
+        ]
+    );
+}
+
+#[test]
+fn unreachable_code() {
+    assert_eq!(
+        lints("fun f() {\n    return 1;\n    print 2;\n}\n"),
+        vec![LinterError::UnreachableCode(2, 5)]
+    );
+    assert_eq!(
+        lints(
+            "fun f(a) {\n    if (a) return 1; else {\n        return 2;\n    }\n    print 3;\n}\n"
+        ),
+        vec![LinterError::UnreachableCode(2, 12)]
+    );
+    // lox/test/return/after_if.lox and friends return from one branch only.
+    assert_eq!(
+        lints("fun f() {\n    if (false) \"no\"; else return \"ok\";\n    print 1;\n}\n"),
+        vec![]
+    );
+    assert_eq!(
+        lints("fun f() {\n    while (true) return \"ok\";\n}\n"),
+        vec![]
+    );
+}
+
+#[test]
+fn self_assignment() {
+    assert_eq!(
+        lints("var a;\na = a;\nthis.b.c = this.b.c;\na.b = a.c;\n"),
+        vec![
+            LinterError::SelfAssignment(2, 1, "a".to_string()),
+            LinterError::SelfAssignment(3, 8, "this.b.c".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn chained_assignment_is_not_self_assignment() {
+    assert_eq!(
+        lints("var a;\nvar b;\na = a = b;\na.c = a.c = b;\n"),
+        vec![]
+    );
+}
+
+#[test]
+fn constant_comparisons() {
+    assert_eq!(
+        lints("print 1 < 2;\nprint \"a\" == \"b\";\nprint nil != 1;\nprint 1 + 2;\nprint \"a\" < 1;\n"),
+        vec![
+            LinterError::ConstantComparison(1, 9, true),
+            LinterError::ConstantComparison(2, 11, false),
+            LinterError::ConstantComparison(3, 11, true),
+        ]
+    );
+    // lox/test/number/nan_equality.lox shows why these aren't constants.
+    assert_eq!(
+        lints("var nan = 0/0;\nprint nan == nan;\nprint nan != nan;\nprint nan == 0;\n"),
+        vec![
+            LinterError::SelfComparison(2, 11, "nan".to_string(), true),
+            LinterError::SelfComparison(3, 11, "nan".to_string(), false),
+        ]
+    );
+}
+
+#[test]
+fn properties_compared_with_themselves() {
+    // Each get of a method binds it anew, and a getter could return anything.
+    assert_eq!(
+        lints(
+            "class A {\n    m() {\n        return this == this and this.m == this.m;\n    }\n}\n"
+        ),
+        vec![LinterError::SelfComparison(3, 21, "this".to_string(), true)]
+    );
+}
+
+#[test]
+fn skipped_when_the_source_does_not_parse() {
+    assert_eq!(lints("{\n    var a;\n"), vec![]);
+}
@@ -0,0 +1,280 @@
+//! Rules that only look at the whitespace tokens of a source file.
+
+use crate::error::LinterError;
+use crate::linter::fix::Fix;
+use crate::linter::rule::{LintContext, LintRule, Severity};
+use crate::scanner::tokens::{LoxToken, TokenType};
+
+/// How line endings are written in [LinterError::InconsistentLineEnding].
+const CRLF: &str = "\\r\\n";
+const LF: &str = "\\n";
+
+/// A line of tokens, without its line ending.
+struct Line<'a> {
+    tokens: &'a [LoxToken],
+    /// The `\r` or `\n` token the line ending starts with, and how it is written.
+    ending: Option<(&'a LoxToken, &'static str)>,
+}
+
+impl<'a> Line<'a> {
+    /// Where the non-whitespace tokens start and end, `None` for a blank line.
+    fn content(&self) -> Option<(usize, usize)> {
+        let start = self.tokens.iter().position(|t| !t.is_whitespace())?;
+        let end = self.tokens.iter().rposition(|t| !t.is_whitespace())? + 1;
+        Some((start, end))
+    }
+}
+
+fn lines(tokens: &[LoxToken]) -> impl Iterator<Item = Line<'_>> {
+    tokens
+        .split_inclusive(|t| t.token_type == TokenType::Linefeed)
+        .map(|line| match line {
+            [rest @ .., cr, lf]
+                if cr.token_type == TokenType::CarriageReturn
+                    && lf.token_type == TokenType::Linefeed =>
+            {
+                Line {
+                    tokens: rest,
+                    ending: Some((cr, CRLF)),
+                }
+            }
+            [rest @ .., lf] if lf.token_type == TokenType::Linefeed => Line {
+                tokens: rest,
+                ending: Some((lf, LF)),
+            },
+            line => Line {
+                tokens: line,
+                ending: None,
+            },
+        })
+}
+
+/// Every line should end the same way as the first one.
+pub struct LineEndings;
+
+impl LintRule for LineEndings {
+    fn id(&self) -> &'static str {
+        "line-endings"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        let mut endings = lines(context.tokens).filter_map(|line| line.ending);
+        let expected = match endings.next() {
+            Some((_, expected)) => expected,
+            None => return Vec::new(),
+        };
+        endings
+            .filter(|(_, ending)| *ending != expected)
+            .map(|(token, ending)| {
+                LinterError::InconsistentLineEnding(token.line, token.column, ending, expected)
+            })
+            .collect()
+    }
+
+    fn fix(&self, error: &LinterError, context: &LintContext) -> Option<Fix> {
+        let (line, column, ending, expected) = match *error {
+            LinterError::InconsistentLineEnding(line, column, ending, expected) => {
+                (line, column, ending, expected)
+            }
+            _ => return None,
+        };
+        let start = context.token_at(line, column)?;
+        let length = if ending == CRLF { 2 } else { 1 };
+        Some(Fix {
+            start: (line, column),
+            end: context.position(start + length),
+            replacement: if expected == CRLF { "\r\n" } else { "\n" }.to_string(),
+        })
+    }
+}
+
+pub struct NoTabs;
+
+impl LintRule for NoTabs {
+    fn id(&self) -> &'static str {
+        "no-tabs"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        context
+            .tokens
+            .iter()
+            .filter(|t| t.token_type == TokenType::Tab)
+            .map(|t| LinterError::TabDetected(t.line, t.column))
+            .collect()
+    }
+
+    /// Tabs in indentation become a level of indentation, others a single space.
+    fn fix(&self, error: &LinterError, context: &LintContext) -> Option<Fix> {
+        let (line, column) = match *error {
+            LinterError::TabDetected(line, column) => (line, column),
+            _ => return None,
+        };
+        let tab = context.token_at(line, column)?;
+        let indenting = context.tokens[..tab]
+            .iter()
+            .rev()
+            .take_while(|t| t.token_type != TokenType::Linefeed)
+            .all(|t| t.is_whitespace());
+        let width = if indenting {
+            context.config.indent_width
+        } else {
+            1
+        };
+        Some(Fix {
+            start: (line, column),
+            end: context.position(tab + 1),
+            replacement: " ".repeat(width),
+        })
+    }
+}
+
+/// Indentation made of spaces has to be a multiple of the configured width, tabs are left to
+/// [NoTabs].
+pub struct IndentationWidth;
+
+impl LintRule for IndentationWidth {
+    fn id(&self) -> &'static str {
+        "indentation-width"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        let width = context.config.indent_width;
+        lines(context.tokens)
+            .filter_map(|line| {
+                let (start, _) = line.content()?;
+                let indentation = &line.tokens[..start];
+                let content = &line.tokens[start];
+                (indentation.iter().all(|t| t.token_type == TokenType::Space)
+                    && !indentation.len().is_multiple_of(width))
+                .then_some(LinterError::IndentationWidth(
+                    content.line,
+                    content.column,
+                    indentation.len(),
+                    width,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Whitespace at the end of a line, or anywhere on a blank line.
+pub struct TrailingWhitespace;
+
+impl LintRule for TrailingWhitespace {
+    fn id(&self) -> &'static str {
+        "trailing-whitespace"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        lines(context.tokens)
+            .filter_map(|line| {
+                let trailing = match line.content() {
+                    Some((_, end)) => line.tokens.get(end),
+                    None => line.tokens.first(),
+                }?;
+                Some(LinterError::TrailingWhitespace(
+                    trailing.line,
+                    trailing.column,
+                ))
+            })
+            .collect()
+    }
+
+    fn fix(&self, error: &LinterError, context: &LintContext) -> Option<Fix> {
+        let (line, column) = match *error {
+            LinterError::TrailingWhitespace(line, column) => (line, column),
+            _ => return None,
+        };
+        let start = context.token_at(line, column)?;
+        let end = start
+            + context.tokens[start..]
+                .iter()
+                .take_while(|t| t.token_type != TokenType::Linefeed && t.is_whitespace())
+                .count();
+        // The \r of a \r\n line ending stays.
+        let end = match context.tokens.get(end) {
+            Some(lf) if lf.token_type == TokenType::Linefeed && end > start => {
+                match context.tokens[end - 1].token_type {
+                    TokenType::CarriageReturn => end - 1,
+                    _ => end,
+                }
+            }
+            _ => end,
+        };
+        Some(Fix {
+            start: (line, column),
+            end: context.position(end),
+            replacement: String::new(),
+        })
+    }
+}
+
+/// Runs of spaces between tokens, indentation and trailing whitespace have their own rules.
+pub struct DoubleSpace;
+
+impl LintRule for DoubleSpace {
+    fn id(&self) -> &'static str {
+        "double-space"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, context: &LintContext) -> Vec<LinterError> {
+        let mut errors = Vec::new();
+        for line in lines(context.tokens) {
+            let (start, end) = match line.content() {
+                Some(content) => content,
+                None => continue,
+            };
+            // Each run is reported once, at its first space.
+            let mut previous_was_space = false;
+            for pair in line.tokens[start..end].windows(2) {
+                let both_spaces = pair.iter().all(|t| t.token_type == TokenType::Space);
+                if both_spaces && !previous_was_space {
+                    errors.push(LinterError::DoubleSpaceDetected(
+                        pair[0].line,
+                        pair[0].column,
+                    ));
+                }
+                previous_was_space = both_spaces;
+            }
+        }
+        errors
+    }
+
+    fn fix(&self, error: &LinterError, context: &LintContext) -> Option<Fix> {
+        let (line, column) = match *error {
+            LinterError::DoubleSpaceDetected(line, column) => (line, column),
+            _ => return None,
+        };
+        let start = context.token_at(line, column)?;
+        let spaces = context.tokens[start..]
+            .iter()
+            .take_while(|t| t.token_type == TokenType::Space)
+            .count();
+        Some(Fix {
+            start: (line, column),
+            end: context.position(start + spaces),
+            replacement: " ".to_string(),
+        })
+    }
+}
@@ -1,84 +1,333 @@
-use std::fs::read_to_string;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use rustyline::error::ReadlineError;
-use rustyline::{Config, Editor, Helper};
+use rustyline::{Config, Editor};
 
-use scanner::scan;
-
-use crate::error::LinterError;
-use crate::error::{LoxError, LoxResult};
-use crate::scanner::tokens::{LoxToken, TokenType};
-
-mod error;
-mod linter;
-mod scanner;
-mod utils;
+use darmok_lox::linter::{lox_files, LintConfig, LintRegistry, Linter, OutputFormat};
+use darmok_lox::repl::{needs_more_input, LoxHelper, MetaCommand, ReplConfig};
+use darmok_lox::{format_range, format_source, Lox, LoxError, LoxResult, DEFAULT_MAX_CALL_DEPTH};
 
 #[derive(Parser, Debug)]
-#[clap(about, version, author)]
+#[clap(about, version, author, args_conflicts_with_subcommands = true)]
 struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     file_names: Vec<String>,
+
+    /// How many Lox calls may be nested before raising a stack overflow error.
+    #[clap(long, default_value_t = DEFAULT_MAX_CALL_DEPTH)]
+    max_call_depth: usize,
+
+    /// REPL config file to use instead of the one in the user's config directory.
+    #[clap(long)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Lints scripts, with the settings of the `.loxlint.toml` in the current directory if there
+    /// is one.
+    Lint {
+        /// Applies the fixes of findings that have one, rewriting the files in place.
+        #[clap(long)]
+        fix: bool,
+
+        /// How findings are printed: text, sarif or checkstyle.
+        #[clap(long, default_value = "text")]
+        format: OutputFormat,
+
+        /// Scripts, or directories to search for `*.lox` files that aren't listed in a
+        /// `.loxlintignore` or `.gitignore`.
+        paths: Vec<String>,
+    },
+    /// Formats scripts, printing them unless `--check` or `--write` is given.
+    Fmt {
+        /// Lists the scripts that aren't formatted, failing if there are any.
+        #[clap(long, conflicts_with = "write")]
+        check: bool,
+
+        /// Rewrites the scripts that aren't formatted.
+        #[clap(long)]
+        write: bool,
+
+        /// Formats just the statements in this byte range, such as `120..480`, leaving the ones
+        /// with syntax errors as they are rather than failing.
+        #[clap(long, parse(try_from_str = parse_range))]
+        range: Option<Range<usize>>,
+
+        /// Scripts, or directories to search for `*.lox` files as `lint` does.
+        paths: Vec<String>,
+    },
 }
 
 fn main() {
     let args = Args::parse();
 
+    if let Some(Command::Fmt {
+        check,
+        write,
+        range,
+        paths,
+    }) = &args.command
+    {
+        if run_fmt(paths, *check, *write, range.clone()) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(Command::Lint { fix, format, paths }) = &args.command {
+        match run_lint(paths, *fix, *format) {
+            Ok(true) => std::process::exit(1),
+            Ok(false) => {}
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(exit_code(&error));
+            }
+        }
+        return;
+    }
+
     if args.file_names.is_empty() {
-        run_prompt();
+        let config = load_config(args.config);
+        run_prompt(args.max_call_depth, &config);
     }
 
-    run_files(&args.file_names).unwrap();
+    run_files(&args.file_names, args.max_call_depth).unwrap();
 }
 
-fn run_prompt() {
-    let mut rl = setup_rustyline();
+/// Every line runs in the same session, so variables, functions and classes stick around until
+/// the REPL exits or is `:reset`.  Lines starting with `:` are [meta-commands](MetaCommand).
+fn run_prompt(max_call_depth: usize, config: &ReplConfig) {
+    let mut rl = setup_rustyline(config);
+    let history_path = config.history_path();
+    if let Some(path) = &history_path {
+        // There is no history yet the first time the REPL runs.
+        let _ = rl.load_history(path);
+    }
+
+    let mut lox = Lox::with_max_call_depth(max_call_depth);
+    setup_interrupt(lox.interrupt_handle());
     loop {
-        let line = match rl.readline("> ") {
-            Ok(l) => l,
+        if let Some(helper) = rl.helper_mut() {
+            helper.set_globals(lox.globals().into_iter().map(|(name, _)| name));
+        }
+        let line = match read_input(&mut rl, config) {
+            Ok(l) => {
+                rl.add_history_entry(&l);
+                l
+            }
             Err(ReadlineError::Eof) => {
+                if let Some(path) = &history_path {
+                    save_history(&mut rl, path);
+                }
                 println!("Goodbye!");
                 return;
             }
+            // Ctrl-C while editing just discards the input, like most shells.
+            Err(ReadlineError::Interrupted) => continue,
             Err(err) => {
                 eprintln!("Error reading line: {:?}", err);
                 String::new()
             }
         };
-        run(&line, false)
+        if let Some(command) = MetaCommand::parse(&line) {
+            if let Err(error) = command.and_then(|c| c.run(&mut lox, &mut std::io::stdout())) {
+                lox.report(&error);
+            }
+            continue;
+        }
+        match lox.eval_line(&line) {
+            Ok(Some(value)) => println!("{}", value),
+            Ok(None) => {}
+            Err(error) => lox.report(&error),
+        }
     }
 }
 
-fn setup_rustyline() -> Editor<()> {
-    let mut rl = Editor::<()>::with_config(Config::builder().auto_add_history(true).build());
-    setup_history(&mut rl);
+/// Reads lines until they form a complete input, an open bracket, string or comment continues it
+/// on the next line.
+fn read_input(rl: &mut Editor<LoxHelper>, config: &ReplConfig) -> Result<String, ReadlineError> {
+    let mut input = rl.readline(&config.prompt)?;
+    while needs_more_input(&input) {
+        set_pending_input(rl, &input);
+        let line = rl.readline(&config.continuation_prompt);
+        set_pending_input(rl, "");
+        input.push('\n');
+        input.push_str(&line?);
+    }
+    Ok(input)
+}
+
+/// Lets the helper match brackets across the lines of a multi-line input.
+fn set_pending_input(rl: &mut Editor<LoxHelper>, pending: &str) {
+    if let Some(helper) = rl.helper_mut() {
+        helper.set_pending(pending);
+    }
+}
+
+fn setup_rustyline(config: &ReplConfig) -> Editor<LoxHelper> {
+    // Multi-line inputs are added to the history as a whole once complete.
+    let rl_config = Config::builder()
+        .auto_add_history(false)
+        .max_history_size(config.history_size)
+        .edit_mode(config.edit_mode.into())
+        .build();
+    let mut rl = Editor::with_config(rl_config);
+    let mut helper = LoxHelper::new();
+    helper.set_colour(config.colour);
+    rl.set_helper(Some(helper));
     rl
 }
 
-fn setup_history(rl: &mut Editor<impl Helper>) {
-    if let Err(e) = rl.load_history(".repl_history") {
-        if let Err(e) = std::fs::File::create(".repl_history") {
-            eprintln!("Cannot create history file! {}", e)
-        }
+/// Reads the REPL config, falling back to the defaults (after saying why) if it is broken.
+fn load_config(path: Option<PathBuf>) -> ReplConfig {
+    let path = match path.or_else(ReplConfig::default_path) {
+        Some(path) => path,
+        None => return ReplConfig::default(),
+    };
+    ReplConfig::load(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        ReplConfig::default()
+    })
+}
+
+/// Ctrl-C while a line is running stops it and returns to the prompt instead of killing the REPL.
+fn setup_interrupt(interrupt: Arc<AtomicBool>) {
+    if let Err(e) = ctrlc::set_handler(move || interrupt.store(true, Ordering::Relaxed)) {
+        eprintln!("Cannot install Ctrl-C handler! {}", e)
+    }
+}
+
+fn save_history(rl: &mut Editor<LoxHelper>, path: &Path) {
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Err(e) = rl.save_history(path) {
+        eprintln!("Cannot save history to {}! {}", path.display(), e)
     }
 }
 
-fn run_files(file_names: &[String]) -> anyhow::Result<()> {
+fn run_files(file_names: &[String], max_call_depth: usize) -> anyhow::Result<()> {
     for file_name in file_names {
-        let s = read_to_string(file_name)?;
-        run(&s, false);
+        let mut lox = Lox::with_max_call_depth(max_call_depth);
+        if let Err(error) = lox.run_file(file_name) {
+            lox.report(&error);
+            std::process::exit(exit_code(&error));
+        }
     }
     Ok(())
 }
 
-fn run(script: &str, _enable_linting: bool) {
-    let tokens = scan(script);
+/// Prints every finding in the scripts under the paths, after fixing the ones that can be if `fix`
+/// is set.  Returns whether any finding is an error or a script couldn't be linted.
+fn run_lint(paths: &[String], fix: bool, format: OutputFormat) -> LoxResult<bool> {
+    let config = LintConfig::load(LintConfig::FILE_NAME)?;
+    let linter = Linter::new(LintRegistry::standard(), config)?;
+    let (files, failures) = lox_files(paths);
+    if fix {
+        for (file, result) in files.iter().zip(linter.fix_files(&files)) {
+            match result {
+                // Kept off stdout, which may be a SARIF or checkstyle document.
+                Ok(applied) if applied > 0 => {
+                    eprintln!("{}: fixed {} problems", file.display(), applied)
+                }
+                Ok(_) => {}
+                Err(error) => eprintln!("{}", error),
+            }
+        }
+    }
+    let mut report = linter.lint_files(&files);
+    for (path, error) in failures {
+        report.fail(path, error);
+    }
+    print!("{}", report.render(format));
+    if format == OutputFormat::Text {
+        print!("{}", report.summary());
+    } else {
+        eprint!("{}", report.summary());
+    }
+    Ok(report.has_errors())
+}
+
+fn parse_range(range: &str) -> Result<Range<usize>, String> {
+    let invalid = || format!("expected a range such as 120..480, not '{}'", range);
+    let (start, end) = range.split_once("..").ok_or_else(invalid)?;
+    let start = start.parse().map_err(|_| invalid())?;
+    let end = end.parse().map_err(|_| invalid())?;
+    if start > end {
+        return Err(invalid());
+    }
+    Ok(start..end)
+}
 
-    for token in tokens {
-        if let Ok(t) = token {
-            println!("{:?}", t);
+/// Formats the scripts under the paths, returning whether any couldn't be read, formatted or
+/// written or, with `check`, weren't formatted.  A script with syntax errors is reported and
+/// skipped unless just a `range` of it is formatted, the other scripts are still formatted.
+fn run_fmt(paths: &[String], check: bool, write: bool, range: Option<Range<usize>>) -> bool {
+    let (files, failures) = lox_files(paths);
+    let mut failed = !failures.is_empty();
+    for (_, error) in failures {
+        eprintln!("{}", error);
+    }
+    for file in files {
+        let io_error =
+            |e: std::io::Error| LoxError::IoError(file.display().to_string(), e.to_string());
+        let source = match std::fs::read_to_string(&file) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("{}", io_error(e));
+                failed = true;
+                continue;
+            }
+        };
+        let formatted = match range.clone() {
+            Some(range) => Ok(format_range(&source, range)),
+            None => format_source(&source),
+        };
+        let formatted = match formatted {
+            Ok(formatted) => formatted,
+            Err(error) => {
+                eprintln!("{}: {}", file.display(), error);
+                failed = true;
+                continue;
+            }
+        };
+        if check {
+            if formatted != source {
+                println!("{}", file.display());
+                failed = true;
+            }
+        } else if write {
+            if formatted != source {
+                if let Err(e) = std::fs::write(&file, formatted) {
+                    eprintln!("{}", io_error(e));
+                    failed = true;
+                }
+            }
         } else {
-            eprintln!("{:?}", token);
+            print!("{}", formatted);
         }
     }
+    failed
+}
+
+/// Exit codes from sysexits.h, the same ones the reference Lox implementations use.
+fn exit_code(error: &LoxError) -> i32 {
+    const EX_DATAERR: i32 = 65;
+    const EX_NOINPUT: i32 = 66;
+    const EX_SOFTWARE: i32 = 70;
+    const EX_CONFIG: i32 = 78;
+
+    match error {
+        LoxError::IoError(..) => EX_NOINPUT,
+        LoxError::InvalidConfig(..) => EX_CONFIG,
+        e if e.is_compile_error() => EX_DATAERR,
+        _ => EX_SOFTWARE,
+    }
 }
@@ -0,0 +1,131 @@
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::scanner::tokens::LoxToken;
+
+/// Unique identifier of an expression that refers to a variable, used by the resolver to record
+/// how many scopes away the variable was declared.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExprId(usize);
+
+impl ExprId {
+    /// Ids are handed out from a process wide counter so that ASTs parsed at different times (such
+    /// as successive REPL lines) never collide.
+    pub fn next() -> ExprId {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        ExprId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+    Nil,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Assign {
+        id: ExprId,
+        name: LoxToken,
+        value: Box<Expr>,
+    },
+    Binary {
+        left: Box<Expr>,
+        operator: LoxToken,
+        right: Box<Expr>,
+    },
+    Call {
+        callee: Box<Expr>,
+        paren: LoxToken,
+        arguments: Vec<Expr>,
+    },
+    Get {
+        object: Box<Expr>,
+        name: LoxToken,
+    },
+    Grouping(Box<Expr>),
+    Literal(Literal),
+    Logical {
+        left: Box<Expr>,
+        operator: LoxToken,
+        right: Box<Expr>,
+    },
+    Set {
+        object: Box<Expr>,
+        name: LoxToken,
+        value: Box<Expr>,
+    },
+    Super {
+        id: ExprId,
+        keyword: LoxToken,
+        method: LoxToken,
+    },
+    This {
+        id: ExprId,
+        keyword: LoxToken,
+    },
+    Unary {
+        operator: LoxToken,
+        right: Box<Expr>,
+    },
+    Variable {
+        id: ExprId,
+        name: LoxToken,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Stmt {
+    Block(Vec<Stmt>),
+    Class(ClassDeclaration),
+    Expression(Expr),
+    Function(Rc<FunctionDeclaration>),
+    If {
+        condition: Expr,
+        then_branch: Box<Stmt>,
+        else_branch: Option<Box<Stmt>>,
+    },
+    Print(Expr),
+    Return {
+        keyword: LoxToken,
+        value: Option<Expr>,
+    },
+    Var {
+        name: LoxToken,
+        initializer: Option<Expr>,
+    },
+    /// `for` loops are desugared into these too, in which case the keyword is `for`.
+    While {
+        keyword: LoxToken,
+        condition: Expr,
+        body: Box<Stmt>,
+    },
+}
+
+/// A line typed into the REPL.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplInput {
+    /// A bare expression without a trailing `;`, whose value gets printed.
+    Expression(Expr),
+    Statements(Vec<Stmt>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClassDeclaration {
+    pub name: LoxToken,
+    /// Always an [Expr::Variable] when present.
+    pub superclass: Option<Expr>,
+    pub methods: Vec<Rc<FunctionDeclaration>>,
+}
+
+/// Function declarations are reference counted because every closure created from them keeps the
+/// declaration alive after the statement list that contained it is gone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionDeclaration {
+    pub name: LoxToken,
+    pub params: Vec<LoxToken>,
+    pub body: Vec<Stmt>,
+}
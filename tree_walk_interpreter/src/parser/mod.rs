@@ -0,0 +1,622 @@
+use std::rc::Rc;
+
+use crate::error::{LoxError, LoxResult, ParserError};
+use crate::parser::ast::{
+    ClassDeclaration, Expr, ExprId, FunctionDeclaration, Literal, ReplInput, Stmt,
+};
+use crate::scanner::tokens::{LoxToken, TokenType};
+
+pub mod ast;
+mod printer;
+
+#[cfg(test)]
+mod tests;
+
+const MAX_ARGUMENTS: usize = 255;
+
+/// Parse the scanned tokens into a list of [statements](Stmt).
+///
+/// Tokens the scanner failed on, comments and whitespace are skipped, the scanner errors are
+/// expected to have been reported by the caller.  On failure every parser error found is returned,
+/// the parser synchronizes on statement boundaries so one mistake doesn't hide the rest.
+pub fn parse(tokens: &[LoxResult<LoxToken>]) -> Result<Vec<Stmt>, Vec<LoxError>> {
+    Parser::new(significant_tokens(tokens)).parse()
+}
+
+/// Parse a line typed into the REPL, which is either a single expression without a trailing `;`
+/// or a list of statements just like [parse] accepts.
+pub fn parse_repl(tokens: &[LoxResult<LoxToken>]) -> Result<ReplInput, Vec<LoxError>> {
+    Parser::new(significant_tokens(tokens)).parse_repl()
+}
+
+fn significant_tokens(tokens: &[LoxResult<LoxToken>]) -> Vec<LoxToken> {
+    tokens
+        .iter()
+        .filter_map(|t| t.as_ref().ok())
+        .filter(|t| !t.is_whitespace() && !t.is_comment())
+        .cloned()
+        .collect()
+}
+
+type ParseResult<T> = Result<T, LoxError>;
+
+struct Parser {
+    tokens: Vec<LoxToken>,
+    current: usize,
+    errors: Vec<LoxError>,
+}
+
+impl Parser {
+    fn new(mut tokens: Vec<LoxToken>) -> Parser {
+        if !matches!(tokens.last(), Some(t) if t.token_type == TokenType::Eof) {
+            let (line, column) = tokens.last().map(|t| (t.line, t.column)).unwrap_or((1, 1));
+            tokens.push(LoxToken {
+                token_type: TokenType::Eof,
+                lexeme: "".to_string(),
+                line,
+                column,
+            });
+        }
+        Parser {
+            tokens,
+            current: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<Stmt>, Vec<LoxError>> {
+        let mut statements = Vec::new();
+        while !self.is_at_end() {
+            statements.extend(self.declaration());
+        }
+
+        if self.errors.is_empty() {
+            Ok(statements)
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    /// Tries the input as a lone expression first, anything else (including an expression with
+    /// trailing tokens) is parsed again from the start as statements so the errors make sense.
+    fn parse_repl(mut self) -> Result<ReplInput, Vec<LoxError>> {
+        if !self.is_at_end() {
+            if let Ok(expr) = self.expression() {
+                if self.is_at_end() && self.errors.is_empty() {
+                    return Ok(ReplInput::Expression(expr));
+                }
+            }
+        }
+
+        self.current = 0;
+        self.errors.clear();
+        self.parse().map(ReplInput::Statements)
+    }
+
+    // Statements.
+
+    /// Parses a declaration, recording the error and skipping ahead to the next statement if it is
+    /// malformed.  Recovery happens here so an error inside a block doesn't discard the whole block.
+    fn declaration(&mut self) -> Option<Stmt> {
+        match self.declaration_or_statement() {
+            Ok(stmt) => Some(stmt),
+            Err(e) => {
+                self.errors.push(e);
+                self.synchronize();
+                None
+            }
+        }
+    }
+
+    fn declaration_or_statement(&mut self) -> ParseResult<Stmt> {
+        if self.match_token(&[TokenType::Class]) {
+            return self.class_declaration();
+        }
+        if self.match_token(&[TokenType::Fun]) {
+            return Ok(Stmt::Function(self.function("function")?));
+        }
+        if self.match_token(&[TokenType::Var]) {
+            return self.var_declaration();
+        }
+        self.statement()
+    }
+
+    fn class_declaration(&mut self) -> ParseResult<Stmt> {
+        let name = self.consume(TokenType::Identifier, "Expect class name.")?;
+
+        let superclass = if self.match_token(&[TokenType::Less]) {
+            let name = self.consume(TokenType::Identifier, "Expect superclass name.")?;
+            Some(Expr::Variable {
+                id: ExprId::next(),
+                name,
+            })
+        } else {
+            None
+        };
+
+        self.consume(TokenType::LeftBrace, "Expect '{' before class body.")?;
+
+        let mut methods = Vec::new();
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            methods.push(self.function("method")?);
+        }
+        self.consume(TokenType::RightBrace, "Expect '}' after class body.")?;
+
+        Ok(Stmt::Class(ClassDeclaration {
+            name,
+            superclass,
+            methods,
+        }))
+    }
+
+    fn function(&mut self, kind: &str) -> ParseResult<Rc<FunctionDeclaration>> {
+        let name = self.consume(TokenType::Identifier, &format!("Expect {} name.", kind))?;
+        self.consume(
+            TokenType::LeftParen,
+            &format!("Expect '(' after {} name.", kind),
+        )?;
+
+        let mut params = Vec::new();
+        if !self.check(&TokenType::RightParen) {
+            loop {
+                if params.len() >= MAX_ARGUMENTS {
+                    // Reported but not thrown, the parser is still in a perfectly good state.
+                    let token = self.peek();
+                    self.errors.push(
+                        ParserError::TooManyParameters(
+                            token.line,
+                            token.column,
+                            token.lexeme.clone(),
+                        )
+                        .into(),
+                    );
+                }
+                params.push(self.consume(TokenType::Identifier, "Expect parameter name.")?);
+                if !self.match_token(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume(TokenType::RightParen, "Expect ')' after parameters.")?;
+
+        self.consume(
+            TokenType::LeftBrace,
+            &format!("Expect '{{' before {} body.", kind),
+        )?;
+        let body = self.block()?;
+        Ok(Rc::new(FunctionDeclaration { name, params, body }))
+    }
+
+    fn var_declaration(&mut self) -> ParseResult<Stmt> {
+        let name = self.consume(TokenType::Identifier, "Expect variable name.")?;
+        let initializer = if self.match_token(&[TokenType::Equal]) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+        self.consume(
+            TokenType::Semicolon,
+            "Expect ';' after variable declaration.",
+        )?;
+        Ok(Stmt::Var { name, initializer })
+    }
+
+    fn statement(&mut self) -> ParseResult<Stmt> {
+        if self.match_token(&[TokenType::For]) {
+            return self.for_statement();
+        }
+        if self.match_token(&[TokenType::If]) {
+            return self.if_statement();
+        }
+        if self.match_token(&[TokenType::Print]) {
+            return self.print_statement();
+        }
+        if self.match_token(&[TokenType::Return]) {
+            return self.return_statement();
+        }
+        if self.match_token(&[TokenType::While]) {
+            return self.while_statement();
+        }
+        if self.match_token(&[TokenType::LeftBrace]) {
+            return Ok(Stmt::Block(self.block()?));
+        }
+        self.expression_statement()
+    }
+
+    /// `for` loops are desugared into a `while` loop wrapped in blocks, so the rest of the pipeline
+    /// never sees them.
+    fn for_statement(&mut self) -> ParseResult<Stmt> {
+        let keyword = self.previous().clone();
+        self.consume(TokenType::LeftParen, "Expect '(' after 'for'.")?;
+        let initializer = if self.match_token(&[TokenType::Semicolon]) {
+            None
+        } else if self.match_token(&[TokenType::Var]) {
+            Some(self.var_declaration()?)
+        } else {
+            Some(self.expression_statement()?)
+        };
+
+        let condition = if !self.check(&TokenType::Semicolon) {
+            self.expression()?
+        } else {
+            Expr::Literal(Literal::Bool(true))
+        };
+        self.consume(TokenType::Semicolon, "Expect ';' after loop condition.")?;
+
+        let increment = if !self.check(&TokenType::RightParen) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+        self.consume(TokenType::RightParen, "Expect ')' after for clauses.")?;
+
+        let mut body = self.statement()?;
+        if let Some(increment) = increment {
+            body = Stmt::Block(vec![body, Stmt::Expression(increment)]);
+        }
+        body = Stmt::While {
+            keyword,
+            condition,
+            body: Box::new(body),
+        };
+        if let Some(initializer) = initializer {
+            body = Stmt::Block(vec![initializer, body]);
+        }
+        Ok(body)
+    }
+
+    fn if_statement(&mut self) -> ParseResult<Stmt> {
+        self.consume(TokenType::LeftParen, "Expect '(' after 'if'.")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RightParen, "Expect ')' after if condition.")?;
+
+        let then_branch = Box::new(self.statement()?);
+        let else_branch = if self.match_token(&[TokenType::Else]) {
+            Some(Box::new(self.statement()?))
+        } else {
+            None
+        };
+        Ok(Stmt::If {
+            condition,
+            then_branch,
+            else_branch,
+        })
+    }
+
+    fn print_statement(&mut self) -> ParseResult<Stmt> {
+        let value = self.expression()?;
+        self.consume(TokenType::Semicolon, "Expect ';' after value.")?;
+        Ok(Stmt::Print(value))
+    }
+
+    fn return_statement(&mut self) -> ParseResult<Stmt> {
+        let keyword = self.previous().clone();
+        let value = if !self.check(&TokenType::Semicolon) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+        self.consume(TokenType::Semicolon, "Expect ';' after return value.")?;
+        Ok(Stmt::Return { keyword, value })
+    }
+
+    fn while_statement(&mut self) -> ParseResult<Stmt> {
+        let keyword = self.previous().clone();
+        self.consume(TokenType::LeftParen, "Expect '(' after 'while'.")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RightParen, "Expect ')' after condition.")?;
+        let body = Box::new(self.statement()?);
+        Ok(Stmt::While {
+            keyword,
+            condition,
+            body,
+        })
+    }
+
+    fn block(&mut self) -> ParseResult<Vec<Stmt>> {
+        let mut statements = Vec::new();
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            statements.extend(self.declaration());
+        }
+        self.consume(TokenType::RightBrace, "Expect '}' after block.")?;
+        Ok(statements)
+    }
+
+    fn expression_statement(&mut self) -> ParseResult<Stmt> {
+        let expr = self.expression()?;
+        self.consume(TokenType::Semicolon, "Expect ';' after expression.")?;
+        Ok(Stmt::Expression(expr))
+    }
+
+    // Expressions, from lowest to highest precedence.
+
+    fn expression(&mut self) -> ParseResult<Expr> {
+        self.assignment()
+    }
+
+    fn assignment(&mut self) -> ParseResult<Expr> {
+        let expr = self.or()?;
+
+        if self.match_token(&[TokenType::Equal]) {
+            let equals = self.previous().clone();
+            let value = Box::new(self.assignment()?);
+
+            return match expr {
+                Expr::Variable { name, .. } => Ok(Expr::Assign {
+                    id: ExprId::next(),
+                    name,
+                    value,
+                }),
+                Expr::Get { object, name } => Ok(Expr::Set {
+                    object,
+                    name,
+                    value,
+                }),
+                _ => Err(ParserError::InvalidAssignmentTarget(
+                    equals.line,
+                    equals.column,
+                    equals.lexeme,
+                )
+                .into()),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn or(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.and()?;
+        while self.match_token(&[TokenType::Or]) {
+            let operator = self.previous().clone();
+            let right = self.and()?;
+            expr = Expr::Logical {
+                left: Box::new(expr),
+                operator,
+                right: Box::new(right),
+            };
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.equality()?;
+        while self.match_token(&[TokenType::And]) {
+            let operator = self.previous().clone();
+            let right = self.equality()?;
+            expr = Expr::Logical {
+                left: Box::new(expr),
+                operator,
+                right: Box::new(right),
+            };
+        }
+        Ok(expr)
+    }
+
+    fn equality(&mut self) -> ParseResult<Expr> {
+        self.binary(
+            &[TokenType::BangEqual, TokenType::EqualEqual],
+            Parser::comparison,
+        )
+    }
+
+    fn comparison(&mut self) -> ParseResult<Expr> {
+        self.binary(
+            &[
+                TokenType::Greater,
+                TokenType::GreaterEqual,
+                TokenType::Less,
+                TokenType::LessEqual,
+            ],
+            Parser::term,
+        )
+    }
+
+    fn term(&mut self) -> ParseResult<Expr> {
+        self.binary(&[TokenType::Minus, TokenType::Plus], Parser::factor)
+    }
+
+    fn factor(&mut self) -> ParseResult<Expr> {
+        self.binary(&[TokenType::Slash, TokenType::Star], Parser::unary)
+    }
+
+    /// Parses a left associative binary expression whose operands are parsed by `operand`.
+    fn binary(
+        &mut self,
+        operators: &[TokenType],
+        operand: fn(&mut Parser) -> ParseResult<Expr>,
+    ) -> ParseResult<Expr> {
+        let mut expr = operand(self)?;
+        while self.match_token(operators) {
+            let operator = self.previous().clone();
+            let right = operand(self)?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                operator,
+                right: Box::new(right),
+            };
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> ParseResult<Expr> {
+        if self.match_token(&[TokenType::Bang, TokenType::Minus]) {
+            let operator = self.previous().clone();
+            let right = self.unary()?;
+            return Ok(Expr::Unary {
+                operator,
+                right: Box::new(right),
+            });
+        }
+        self.call()
+    }
+
+    fn call(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.primary()?;
+        loop {
+            if self.match_token(&[TokenType::LeftParen]) {
+                expr = self.finish_call(expr)?;
+            } else if self.match_token(&[TokenType::Dot]) {
+                let name =
+                    self.consume(TokenType::Identifier, "Expect property name after '.'.")?;
+                expr = Expr::Get {
+                    object: Box::new(expr),
+                    name,
+                };
+            } else {
+                break;
+            }
+        }
+        Ok(expr)
+    }
+
+    fn finish_call(&mut self, callee: Expr) -> ParseResult<Expr> {
+        let mut arguments = Vec::new();
+        if !self.check(&TokenType::RightParen) {
+            loop {
+                if arguments.len() >= MAX_ARGUMENTS {
+                    let token = self.peek();
+                    self.errors.push(
+                        ParserError::TooManyArguments(
+                            token.line,
+                            token.column,
+                            token.lexeme.clone(),
+                        )
+                        .into(),
+                    );
+                }
+                arguments.push(self.expression()?);
+                if !self.match_token(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        let paren = self.consume(TokenType::RightParen, "Expect ')' after arguments.")?;
+
+        Ok(Expr::Call {
+            callee: Box::new(callee),
+            paren,
+            arguments,
+        })
+    }
+
+    fn primary(&mut self) -> ParseResult<Expr> {
+        let token = self.peek().clone();
+        let expr = match token.token_type {
+            TokenType::False => Expr::Literal(Literal::Bool(false)),
+            TokenType::True => Expr::Literal(Literal::Bool(true)),
+            TokenType::Nil => Expr::Literal(Literal::Nil),
+            TokenType::Number(n) => Expr::Literal(Literal::Number(n)),
+            TokenType::String(s) => Expr::Literal(Literal::String(s)),
+            TokenType::Super => {
+                self.advance();
+                self.consume(TokenType::Dot, "Expect '.' after 'super'.")?;
+                let method =
+                    self.consume(TokenType::Identifier, "Expect superclass method name.")?;
+                return Ok(Expr::Super {
+                    id: ExprId::next(),
+                    keyword: token,
+                    method,
+                });
+            }
+            TokenType::This => Expr::This {
+                id: ExprId::next(),
+                keyword: token,
+            },
+            TokenType::Identifier => Expr::Variable {
+                id: ExprId::next(),
+                name: token,
+            },
+            TokenType::LeftParen => {
+                self.advance();
+                let expr = self.expression()?;
+                self.consume(TokenType::RightParen, "Expect ')' after expression.")?;
+                return Ok(Expr::Grouping(Box::new(expr)));
+            }
+            _ => return Err(self.error_at_current("Expect expression.")),
+        };
+        self.advance();
+        Ok(expr)
+    }
+
+    // Token stream helpers.
+
+    fn match_token(&mut self, token_types: &[TokenType]) -> bool {
+        if token_types.iter().any(|t| self.check(t)) {
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    fn consume(&mut self, token_type: TokenType, message: &str) -> ParseResult<LoxToken> {
+        if self.check(&token_type) {
+            return Ok(self.advance().clone());
+        }
+        Err(self.error_at_current(message))
+    }
+
+    fn check(&self, token_type: &TokenType) -> bool {
+        if self.is_at_end() {
+            return false;
+        }
+        // Literal token types carry their value, only the kind matters here.
+        std::mem::discriminant(&self.peek().token_type) == std::mem::discriminant(token_type)
+    }
+
+    fn advance(&mut self) -> &LoxToken {
+        if !self.is_at_end() {
+            self.current += 1;
+        }
+        self.previous()
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.peek().token_type == TokenType::Eof
+    }
+
+    fn peek(&self) -> &LoxToken {
+        &self.tokens[self.current]
+    }
+
+    fn previous(&self) -> &LoxToken {
+        &self.tokens[self.current.saturating_sub(1)]
+    }
+
+    fn error_at_current(&self, message: &str) -> LoxError {
+        let token = self.peek();
+        if token.token_type == TokenType::Eof {
+            ParserError::UnexpectedEof(token.line, token.column, message.to_string()).into()
+        } else {
+            ParserError::UnexpectedToken(
+                token.line,
+                token.column,
+                token.lexeme.clone(),
+                message.to_string(),
+            )
+            .into()
+        }
+    }
+
+    /// Discards tokens until the start of what is probably the next statement.
+    fn synchronize(&mut self) {
+        self.advance();
+        while !self.is_at_end() {
+            if self.previous().token_type == TokenType::Semicolon {
+                return;
+            }
+            match self.peek().token_type {
+                TokenType::Class
+                | TokenType::Fun
+                | TokenType::Var
+                | TokenType::For
+                | TokenType::If
+                | TokenType::While
+                | TokenType::Print
+                | TokenType::Return => return,
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+    }
+}
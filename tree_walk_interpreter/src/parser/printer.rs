@@ -0,0 +1,128 @@
+//! Prints the AST as nested s-expressions, in the style of Crafting Interpreters' `AstPrinter`.
+
+use std::fmt::{Display, Formatter};
+
+use crate::parser::ast::{Expr, FunctionDeclaration, Literal, ReplInput, Stmt};
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Literal::Nil => write!(f, "nil"),
+            Literal::Bool(b) => write!(f, "{}", b),
+            // Debug always prints a fraction, like the reference implementation does.
+            Literal::Number(n) => write!(f, "{:?}", n),
+            Literal::String(s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Assign { name, value, .. } => write!(f, "(= {} {})", name.lexeme, value),
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            }
+            | Expr::Logical {
+                left,
+                operator,
+                right,
+            } => write!(f, "({} {} {})", operator.lexeme, left, right),
+            Expr::Call {
+                callee, arguments, ..
+            } => {
+                write!(f, "(call {}", callee)?;
+                write_all(f, arguments)?;
+                write!(f, ")")
+            }
+            Expr::Get { object, name } => write!(f, "(. {} {})", object, name.lexeme),
+            Expr::Grouping(expr) => write!(f, "(group {})", expr),
+            Expr::Literal(literal) => write!(f, "{}", literal),
+            Expr::Set {
+                object,
+                name,
+                value,
+            } => write!(f, "(= (. {} {}) {})", object, name.lexeme, value),
+            Expr::Super { method, .. } => write!(f, "(super {})", method.lexeme),
+            Expr::This { .. } => write!(f, "this"),
+            Expr::Unary { operator, right } => write!(f, "({} {})", operator.lexeme, right),
+            Expr::Variable { name, .. } => write!(f, "{}", name.lexeme),
+        }
+    }
+}
+
+impl Display for Stmt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stmt::Block(statements) => {
+                write!(f, "(block")?;
+                write_all(f, statements)?;
+                write!(f, ")")
+            }
+            Stmt::Class(declaration) => {
+                write!(f, "(class {}", declaration.name.lexeme)?;
+                if let Some(superclass) = &declaration.superclass {
+                    write!(f, " < {}", superclass)?;
+                }
+                for method in &declaration.methods {
+                    write!(f, " {}", method)?;
+                }
+                write!(f, ")")
+            }
+            Stmt::Expression(expr) => write!(f, "(; {})", expr),
+            Stmt::Function(declaration) => write!(f, "{}", declaration),
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                write!(f, "(if {} {}", condition, then_branch)?;
+                if let Some(else_branch) = else_branch {
+                    write!(f, " {}", else_branch)?;
+                }
+                write!(f, ")")
+            }
+            Stmt::Print(expr) => write!(f, "(print {})", expr),
+            Stmt::Return { value, .. } => match value {
+                Some(value) => write!(f, "(return {})", value),
+                None => write!(f, "(return)"),
+            },
+            Stmt::Var { name, initializer } => match initializer {
+                Some(initializer) => write!(f, "(var {} {})", name.lexeme, initializer),
+                None => write!(f, "(var {})", name.lexeme),
+            },
+            Stmt::While {
+                condition, body, ..
+            } => write!(f, "(while {} {})", condition, body),
+        }
+    }
+}
+
+impl Display for FunctionDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<&str> = self.params.iter().map(|p| p.lexeme.as_str()).collect();
+        write!(f, "(fun {} ({})", self.name.lexeme, params.join(" "))?;
+        write_all(f, &self.body)?;
+        write!(f, ")")
+    }
+}
+
+/// One statement per line, or just the expression.
+impl Display for ReplInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplInput::Expression(expr) => write!(f, "{}", expr),
+            ReplInput::Statements(statements) => {
+                let lines: Vec<String> = statements.iter().map(|s| s.to_string()).collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+        }
+    }
+}
+
+/// Writes each item preceded by a space.
+fn write_all(f: &mut Formatter<'_>, items: &[impl Display]) -> std::fmt::Result {
+    items.iter().try_for_each(|item| write!(f, " {}", item))
+}
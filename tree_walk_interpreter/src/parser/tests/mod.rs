@@ -0,0 +1,182 @@
+use crate::error::{LoxError, ParserError};
+use crate::parser::ast::{Expr, Literal, ReplInput, Stmt};
+use crate::parser::{parse, parse_repl};
+use crate::scanner::scan;
+
+mod printer;
+
+#[test]
+fn precedence() {
+    let statements = parse(&scan("print 1 + 2 * 3;")).unwrap();
+    match statements.as_slice() {
+        [Stmt::Print(Expr::Binary { left, right, .. })] => {
+            assert_eq!(**left, Expr::Literal(Literal::Number(1.0)));
+            assert!(matches!(**right, Expr::Binary { .. }));
+        }
+        _ => panic!("unexpected statements {:?}", statements),
+    }
+}
+
+#[test]
+fn comments_are_skipped() {
+    let statements = parse(&scan("// comment\nprint nil; /* block */")).unwrap();
+    assert_eq!(statements, vec![Stmt::Print(Expr::Literal(Literal::Nil))]);
+}
+
+#[test]
+fn function_declaration() {
+    let statements = parse(&scan("fun add(a, b) { return a + b; }")).unwrap();
+    match statements.as_slice() {
+        [Stmt::Function(declaration)] => {
+            assert_eq!(declaration.name.lexeme, "add");
+            assert_eq!(
+                declaration
+                    .params
+                    .iter()
+                    .map(|p| p.lexeme.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["a", "b"]
+            );
+            assert!(matches!(declaration.body.as_slice(), [Stmt::Return { .. }]));
+        }
+        _ => panic!("unexpected statements {:?}", statements),
+    }
+}
+
+#[test]
+fn for_is_desugared_to_while() {
+    let statements = parse(&scan("for (var i = 0; i < 3; i = i + 1) print i;")).unwrap();
+    match statements.as_slice() {
+        [Stmt::Block(block)] => {
+            assert!(matches!(
+                block.as_slice(),
+                [Stmt::Var { .. }, Stmt::While { .. }]
+            ));
+        }
+        _ => panic!("unexpected statements {:?}", statements),
+    }
+}
+
+#[test]
+fn invalid_assignment_target() {
+    assert_eq!(
+        parse(&scan("a + b = c;")),
+        Err(vec![LoxError::ParserError(
+            ParserError::InvalidAssignmentTarget(1, 7, "=".to_string())
+        )])
+    );
+}
+
+#[test]
+fn reports_every_statement_error() {
+    assert_eq!(
+        parse(&scan("print;\nvar 1;")),
+        Err(vec![
+            LoxError::ParserError(ParserError::UnexpectedToken(
+                1,
+                6,
+                ";".to_string(),
+                "Expect expression.".to_string()
+            )),
+            LoxError::ParserError(ParserError::UnexpectedToken(
+                2,
+                5,
+                "1".to_string(),
+                "Expect variable name.".to_string()
+            )),
+        ])
+    );
+}
+
+#[test]
+fn unexpected_eof() {
+    assert_eq!(
+        parse(&scan("{ print 1;")),
+        Err(vec![LoxError::ParserError(ParserError::UnexpectedEof(
+            1,
+            11,
+            "Expect '}' after block.".to_string()
+        ))])
+    );
+}
+
+#[test]
+fn property_assignment() {
+    let statements = parse(&scan("a.b.c = 1;")).unwrap();
+    match statements.as_slice() {
+        [Stmt::Expression(Expr::Set { object, name, .. })] => {
+            assert_eq!(name.lexeme, "c");
+            assert!(matches!(**object, Expr::Get { .. }));
+        }
+        _ => panic!("unexpected statements {:?}", statements),
+    }
+}
+
+#[test]
+fn class_declaration() {
+    let statements = parse(&scan("class Foo { init() {} bar(a) {} }")).unwrap();
+    match statements.as_slice() {
+        [Stmt::Class(declaration)] => {
+            assert_eq!(declaration.name.lexeme, "Foo");
+            assert_eq!(
+                declaration
+                    .methods
+                    .iter()
+                    .map(|m| m.name.lexeme.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["init", "bar"]
+            );
+        }
+        _ => panic!("unexpected statements {:?}", statements),
+    }
+}
+
+#[test]
+fn super_requires_method_name() {
+    assert_eq!(
+        parse(&scan("class A < B { m() { super; } }")),
+        Err(vec![LoxError::ParserError(ParserError::UnexpectedToken(
+            1,
+            26,
+            ";".to_string(),
+            "Expect '.' after 'super'.".to_string()
+        ))])
+    );
+}
+
+#[test]
+fn repl_expression() {
+    match parse_repl(&scan("1 + 2 // sum")).unwrap() {
+        ReplInput::Expression(Expr::Binary { operator, .. }) => assert_eq!(operator.lexeme, "+"),
+        input => panic!("unexpected input {:?}", input),
+    }
+}
+
+#[test]
+fn repl_statements() {
+    assert_eq!(
+        parse_repl(&scan("print nil;")).unwrap(),
+        ReplInput::Statements(vec![Stmt::Print(Expr::Literal(Literal::Nil))])
+    );
+    assert!(matches!(
+        parse_repl(&scan("1 + 2;")).unwrap(),
+        ReplInput::Statements(statements) if statements.len() == 1
+    ));
+    assert_eq!(
+        parse_repl(&scan("")).unwrap(),
+        ReplInput::Statements(vec![])
+    );
+}
+
+#[test]
+fn repl_errors_are_statement_errors() {
+    assert_eq!(
+        parse_repl(&scan("1 + 2 print")),
+        Err(vec![LoxError::ParserError(ParserError::UnexpectedToken(
+            1,
+            7,
+            "print".to_string(),
+            "Expect ';' after expression.".to_string()
+        ))])
+    );
+}
@@ -0,0 +1,43 @@
+use crate::parser::parse_repl;
+use crate::scanner::scan;
+
+fn printed(source: &str) -> String {
+    parse_repl(&scan(source)).unwrap().to_string()
+}
+
+#[test]
+fn expressions() {
+    assert_eq!(
+        printed("(5 - (3 - 1)) + -1"),
+        "(+ (group (- 5.0 (group (- 3.0 1.0)))) (- 1.0))"
+    );
+    assert_eq!(
+        printed("a = b.c(1, \"two\", nil) or !d.e"),
+        "(= a (or (call (. b c) 1.0 \"two\" nil) (! (. d e))))"
+    );
+    assert_eq!(printed("x.y = true"), "(= (. x y) true)");
+}
+
+#[test]
+fn statements() {
+    assert_eq!(
+        printed("var a; var b = 1;\nprint a;\nif (a) b; else { a; }"),
+        "(var a)\n(var b 1.0)\n(print a)\n(if a (; b) (block (; a)))"
+    );
+    assert_eq!(
+        printed("for (var i = 0; i < 2; i = i + 1) print i;"),
+        "(block (var i 0.0) (while (< i 2.0) (block (print i) (; (= i (+ i 1.0))))))"
+    );
+}
+
+#[test]
+fn declarations() {
+    assert_eq!(
+        printed("fun f(a, b) { return a; }\nfun g() { return; }"),
+        "(fun f (a b) (return a))\n(fun g () (return))"
+    );
+    assert_eq!(
+        printed("class B < A { init() { this.x = super.y; } }"),
+        "(class B < A (fun init () (; (= (. this x) (super y)))))"
+    );
+}
@@ -0,0 +1,106 @@
+use std::io::Write;
+use std::time::Instant;
+
+use crate::error::{LoxResult, ReplError};
+use crate::parser::parse_repl;
+use crate::scanner::scan_with_whitespace;
+use crate::session::scan_and_parse;
+use crate::Lox;
+
+const HELP: &str = "\
+:tokens <source>  print the tokens the source scans to
+:ast <source>     print the syntax tree the source parses to
+:env              print every global variable
+:load <file>      run a script in this session
+:reset            forget every global defined so far
+:time <source>    run the source and print how long it took
+:help             print this message";
+
+/// A line typed into the REPL that starts with `:`, which inspects or manages the session instead
+/// of being run as Lox.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetaCommand {
+    Tokens(String),
+    Ast(String),
+    Env,
+    Load(String),
+    Reset,
+    Time(String),
+    Help,
+}
+
+impl MetaCommand {
+    /// Parses a meta-command, or returns `None` if the input is just Lox.
+    pub fn parse(input: &str) -> Option<LoxResult<MetaCommand>> {
+        let command = input.trim().strip_prefix(':')?;
+        let (name, argument) = match command.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (command, ""),
+        };
+
+        let required = |expected: &'static str| {
+            if argument.is_empty() {
+                Err(ReplError::MissingArgument(name.to_string(), expected).into())
+            } else {
+                Ok(argument.to_string())
+            }
+        };
+
+        Some(match name {
+            "tokens" => required("some source").map(MetaCommand::Tokens),
+            "ast" => required("some source").map(MetaCommand::Ast),
+            "env" => Ok(MetaCommand::Env),
+            "load" => required("a file name").map(MetaCommand::Load),
+            "reset" => Ok(MetaCommand::Reset),
+            "time" => required("some source").map(MetaCommand::Time),
+            "help" => Ok(MetaCommand::Help),
+            _ => Err(ReplError::UnknownCommand(name.to_string()).into()),
+        })
+    }
+
+    /// Runs the command against the session, writing what it prints to `out`.  Output of the Lox
+    /// code it runs still goes to the session's stdout.
+    pub fn run(&self, lox: &mut Lox, out: &mut impl Write) -> LoxResult<()> {
+        match self {
+            MetaCommand::Tokens(source) => {
+                for token in scan_with_whitespace(source, true) {
+                    match token {
+                        Ok(token) => {
+                            let _ = writeln!(
+                                out,
+                                "{}:{} {:?} {:?}",
+                                token.line, token.column, token.token_type, token.lexeme
+                            );
+                        }
+                        Err(e) => {
+                            let _ = writeln!(out, "{}", e);
+                        }
+                    }
+                }
+            }
+            MetaCommand::Ast(source) => {
+                let _ = writeln!(out, "{}", scan_and_parse(source, parse_repl)?);
+            }
+            MetaCommand::Env => {
+                for (name, value) in lox.globals() {
+                    let _ = writeln!(out, "{} = {}", name, value);
+                }
+            }
+            MetaCommand::Load(path) => lox.run_file(path)?,
+            MetaCommand::Reset => lox.reset(),
+            MetaCommand::Time(source) => {
+                let start = Instant::now();
+                let value = lox.eval_line(source)?;
+                let elapsed = start.elapsed();
+                if let Some(value) = value {
+                    let _ = writeln!(out, "{}", value);
+                }
+                let _ = writeln!(out, "Took {:?}", elapsed);
+            }
+            MetaCommand::Help => {
+                let _ = writeln!(out, "{}", HELP);
+            }
+        }
+        Ok(())
+    }
+}
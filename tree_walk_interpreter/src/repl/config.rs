@@ -0,0 +1,101 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::LoxResult;
+use crate::utils::{load_toml, parse_toml};
+
+/// Directory under the user's config and data directories that the REPL keeps its files in.
+const APP_DIR: &str = "darmok_lox";
+
+/// Settings for the interactive REPL, read from `config.toml` in the user's config directory
+/// (`$XDG_CONFIG_HOME/darmok_lox` on Linux).  Every setting is optional:
+///
+/// ```toml
+/// history_file = "/home/me/.lox_history"
+/// history_size = 1000
+/// prompt = "> "
+/// continuation_prompt = "... "
+/// colour = true
+/// edit_mode = "vi"
+/// dialect = "lox"
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReplConfig {
+    /// Defaults to `history` in the user's data directory, see [history_path](Self::history_path).
+    pub history_file: Option<PathBuf>,
+    /// How many inputs are remembered, older ones are dropped when the history is saved.
+    pub history_size: usize,
+    pub prompt: String,
+    /// Prompt for the following lines of an input whose brackets are still open.
+    pub continuation_prompt: String,
+    /// Whether input is syntax highlighted.
+    pub colour: bool,
+    pub edit_mode: EditMode,
+    pub dialect: Dialect,
+}
+
+impl Default for ReplConfig {
+    fn default() -> Self {
+        ReplConfig {
+            history_file: None,
+            history_size: 1000,
+            prompt: "> ".to_string(),
+            continuation_prompt: "... ".to_string(),
+            colour: true,
+            edit_mode: EditMode::Emacs,
+            dialect: Dialect::Lox,
+        }
+    }
+}
+
+/// Key bindings used to edit the input.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EditMode {
+    Emacs,
+    Vi,
+}
+
+impl From<EditMode> for rustyline::EditMode {
+    fn from(edit_mode: EditMode) -> Self {
+        match edit_mode {
+            EditMode::Emacs => rustyline::EditMode::Emacs,
+            EditMode::Vi => rustyline::EditMode::Vi,
+        }
+    }
+}
+
+/// The language the REPL runs, only the Lox of Crafting Interpreters so far.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dialect {
+    Lox,
+}
+
+impl ReplConfig {
+    /// Where the config is read from unless another file is given, `None` if the platform has no
+    /// config directory.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(APP_DIR).join("config.toml"))
+    }
+
+    /// Reads the config at `path`, usually [default_path](Self::default_path).
+    pub fn load(path: impl AsRef<Path>) -> LoxResult<ReplConfig> {
+        load_toml(path)
+    }
+
+    pub fn parse(path: impl AsRef<Path>, source: &str) -> LoxResult<ReplConfig> {
+        parse_toml(path, source)
+    }
+
+    /// The configured history file, or `history` in the user's data directory
+    /// (`$XDG_DATA_HOME/darmok_lox` on Linux).  `None` if neither exists, in which case history
+    /// isn't kept between sessions.
+    pub fn history_path(&self) -> Option<PathBuf> {
+        self.history_file
+            .clone()
+            .or_else(|| dirs::data_dir().map(|dir| dir.join(APP_DIR).join("history")))
+    }
+}
@@ -0,0 +1,187 @@
+use std::borrow::Cow;
+
+use rustyline::completion::Completer;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{Context, Helper};
+
+use crate::repl::{input_state, InputState};
+use crate::scanner::scan_with_whitespace;
+use crate::scanner::tokens::{LoxToken, TokenType};
+use crate::scanner::KEYWORDS;
+use crate::utils::is_alpha_numeric;
+
+const RESET: &str = "\x1b[0m";
+const KEYWORD: &str = "\x1b[1;34m";
+const LITERAL: &str = "\x1b[35m";
+const STRING: &str = "\x1b[32m";
+const NUMBER: &str = "\x1b[33m";
+const COMMENT: &str = "\x1b[90m";
+
+/// Line editing support for the REPL: syntax highlighting, completion and hints for keywords and
+/// globals, and rejecting input with mismatched brackets.
+#[derive(Debug, Default)]
+pub struct LoxHelper {
+    /// Names of the session's globals, kept up to date by the REPL after every input.
+    globals: Vec<String>,
+    /// The lines of a multi-line input read so far, brackets are matched across all of them.
+    pending: String,
+    /// Turns off syntax highlighting, for terminals without colour.
+    plain: bool,
+}
+
+impl LoxHelper {
+    pub fn new() -> LoxHelper {
+        LoxHelper::default()
+    }
+
+    pub fn set_globals(&mut self, globals: impl IntoIterator<Item = String>) {
+        self.globals = globals.into_iter().collect();
+    }
+
+    /// Whether input and hints are highlighted, they are unless turned off.
+    pub fn set_colour(&mut self, colour: bool) {
+        self.plain = !colour;
+    }
+
+    /// Sets the lines read so far when prompting for the continuation of an input.
+    pub fn set_pending(&mut self, pending: &str) {
+        self.pending = pending.to_string();
+    }
+
+    /// Keywords and globals starting with the word before `pos`, along with where that word
+    /// starts.
+    pub fn completions(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let start = line[..pos]
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| !is_alpha_numeric(c))
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let word = &line[start..pos];
+        if word.is_empty() {
+            return (pos, Vec::new());
+        }
+
+        let mut candidates: Vec<String> = KEYWORDS
+            .keys()
+            .map(|keyword| keyword.to_string())
+            .chain(self.globals.iter().cloned())
+            .filter(|candidate| candidate.starts_with(word) && candidate != word)
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        (start, candidates)
+    }
+
+    /// The line with ANSI colour codes around each token, or as it was if it can't be scanned.
+    pub fn highlighted(&self, line: &str) -> String {
+        let tokens: Result<Vec<LoxToken>, _> =
+            scan_with_whitespace(line, false).into_iter().collect();
+        let tokens = match tokens {
+            Ok(tokens) => tokens,
+            Err(_) => return line.to_string(),
+        };
+
+        let mut highlighted = String::with_capacity(line.len() * 2);
+        for token in &tokens {
+            match colour(token) {
+                Some(colour) => {
+                    highlighted.push_str(colour);
+                    highlighted.push_str(&token.lexeme);
+                    highlighted.push_str(RESET);
+                }
+                None => highlighted.push_str(&token.lexeme),
+            }
+        }
+        highlighted
+    }
+
+    /// Input whose brackets don't match, together with the lines before it, is refused with a
+    /// message pointing at the bracket.
+    pub fn validation(&self, line: &str) -> ValidationResult {
+        let input = if self.pending.is_empty() {
+            line.to_string()
+        } else {
+            format!("{}\n{}", self.pending, line)
+        };
+        match input_state(&input) {
+            InputState::UnmatchedBracket(token) => ValidationResult::Invalid(Some(format!(
+                "\nUnmatched '{}' at {}:{}",
+                token.lexeme, token.line, token.column
+            ))),
+            InputState::Complete | InputState::Incomplete => ValidationResult::Valid(None),
+        }
+    }
+}
+
+fn colour(token: &LoxToken) -> Option<&'static str> {
+    match token.token_type {
+        TokenType::Nil | TokenType::True | TokenType::False => Some(LITERAL),
+        TokenType::String(_) => Some(STRING),
+        TokenType::Number(_) => Some(NUMBER),
+        TokenType::Comment | TokenType::BlockComment => Some(COMMENT),
+        TokenType::Identifier => None,
+        _ if KEYWORDS.contains_key(token.lexeme.as_str()) => Some(KEYWORD),
+        _ => None,
+    }
+}
+
+impl Completer for LoxHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.completions(line, pos))
+    }
+}
+
+impl Hinter for LoxHelper {
+    type Hint = String;
+
+    /// The rest of the only completion, if there is exactly one, when typing at the end of the
+    /// line.
+    fn hint(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> Option<String> {
+        if pos < line.len() {
+            return None;
+        }
+        match self.completions(line, pos) {
+            (start, candidates) if candidates.len() == 1 => {
+                Some(candidates[0][pos - start..].to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Highlighter for LoxHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        if self.plain {
+            return Cow::Borrowed(line);
+        }
+        Cow::Owned(self.highlighted(line))
+    }
+
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        if self.plain {
+            return Cow::Borrowed(hint);
+        }
+        Cow::Owned(format!("{}{}{}", COMMENT, hint, RESET))
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize) -> bool {
+        !self.plain
+    }
+}
+
+impl Validator for LoxHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        Ok(self.validation(ctx.input()))
+    }
+}
+
+impl Helper for LoxHelper {}
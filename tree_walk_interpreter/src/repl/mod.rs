@@ -0,0 +1,60 @@
+use crate::error::{LoxError, ScannerError};
+use crate::scanner::scan_with_whitespace;
+use crate::scanner::tokens::{LoxToken, TokenType};
+
+pub use commands::MetaCommand;
+pub use config::{Dialect, EditMode, ReplConfig};
+pub use helper::LoxHelper;
+
+mod commands;
+mod config;
+mod helper;
+
+#[cfg(test)]
+mod tests;
+
+/// Whether input typed into the REPL is ready to run.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputState {
+    Complete,
+    /// A `(` or `{` is still open, or a string or block comment hasn't been terminated yet.
+    Incomplete,
+    /// A closing bracket that doesn't close the innermost open bracket.
+    UnmatchedBracket(LoxToken),
+}
+
+/// Works out whether the input is complete from its brackets, anything else wrong with it is left
+/// for the parser to report.
+pub fn input_state(input: &str) -> InputState {
+    let mut open_brackets = Vec::new();
+    for token in scan_with_whitespace(input, false) {
+        match token {
+            Ok(token) => match token.token_type {
+                TokenType::LeftParen => open_brackets.push(TokenType::RightParen),
+                TokenType::LeftBrace => open_brackets.push(TokenType::RightBrace),
+                TokenType::RightParen | TokenType::RightBrace => {
+                    let expected = open_brackets.pop();
+                    if expected.as_ref() != Some(&token.token_type) {
+                        return InputState::UnmatchedBracket(token);
+                    }
+                }
+                _ => {}
+            },
+            Err(LoxError::ScannerError(
+                ScannerError::UnterminatedString(..) | ScannerError::UnterminatedBlockComment(..),
+            )) => return InputState::Incomplete,
+            Err(_) => {}
+        }
+    }
+
+    if open_brackets.is_empty() {
+        InputState::Complete
+    } else {
+        InputState::Incomplete
+    }
+}
+
+/// Whether the REPL should keep reading lines before running the input.
+pub fn needs_more_input(input: &str) -> bool {
+    input_state(input) == InputState::Incomplete
+}
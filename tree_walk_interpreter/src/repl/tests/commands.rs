@@ -0,0 +1,101 @@
+use std::io::Write;
+
+use crate::error::{LoxError, ReplError};
+use crate::repl::MetaCommand;
+use crate::{CapturedOutput, Lox, LoxValue};
+
+fn run(lox: &mut Lox, input: &str) -> String {
+    let mut out = Vec::new();
+    MetaCommand::parse(input)
+        .unwrap()
+        .and_then(|command| command.run(lox, &mut out))
+        .unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn parsing() {
+    assert_eq!(MetaCommand::parse("print 1;"), None);
+    assert_eq!(MetaCommand::parse(":env"), Some(Ok(MetaCommand::Env)));
+    assert_eq!(
+        MetaCommand::parse("  :ast  1 + 2 "),
+        Some(Ok(MetaCommand::Ast("1 + 2".to_string())))
+    );
+    assert_eq!(
+        MetaCommand::parse(":load"),
+        Some(Err(LoxError::from(ReplError::MissingArgument(
+            "load".to_string(),
+            "a file name"
+        ))))
+    );
+    assert_eq!(
+        MetaCommand::parse(":nope"),
+        Some(Err(LoxError::from(ReplError::UnknownCommand(
+            "nope".to_string()
+        ))))
+    );
+}
+
+#[test]
+fn tokens() {
+    let mut lox = Lox::new();
+    assert_eq!(
+        run(&mut lox, ":tokens a = \"b\""),
+        "1:1 Identifier \"a\"\n1:3 Equal \"=\"\n1:5 String(\"b\") \"\\\"b\\\"\"\n1:8 Eof \"\"\n"
+    );
+}
+
+#[test]
+fn ast() {
+    let mut lox = Lox::new();
+    assert_eq!(
+        run(&mut lox, ":ast -1 * (2 + 3)"),
+        "(* (- 1.0) (group (+ 2.0 3.0)))\n"
+    );
+    assert_eq!(run(&mut lox, ":ast var a = 1;"), "(var a 1.0)\n");
+    assert!(matches!(
+        MetaCommand::Ast("var;".to_string()).run(&mut lox, &mut Vec::new()),
+        Err(LoxError::CompileErrors(_))
+    ));
+}
+
+#[test]
+fn env_and_reset() {
+    let mut lox = Lox::new();
+    lox.eval("var a = 1; fun f() {}").unwrap();
+    assert_eq!(
+        run(&mut lox, ":env"),
+        "a = 1\nclock = <native fn>\nf = <fn f>\n"
+    );
+
+    run(&mut lox, ":reset");
+    assert_eq!(run(&mut lox, ":env"), "clock = <native fn>\n");
+    assert_eq!(lox.global("a"), None);
+}
+
+#[test]
+fn load() {
+    let path = std::env::temp_dir().join("darmok_lox_repl_load.lox");
+    std::fs::File::create(&path)
+        .unwrap()
+        .write_all(b"var loaded = \"yes\";")
+        .unwrap();
+
+    let mut lox = Lox::new();
+    run(&mut lox, &format!(":load {}", path.display()));
+    assert_eq!(lox.global("loaded"), Some(LoxValue::from("yes")));
+}
+
+#[test]
+fn time() {
+    let mut lox = Lox::new();
+    let stdout = CapturedOutput::new();
+    lox.set_stdout(stdout.clone());
+
+    let printed = run(&mut lox, ":time 1 + 2");
+    assert!(printed.starts_with("3\nTook "), "{}", printed);
+
+    let printed = run(&mut lox, ":time print 4;");
+    assert!(printed.starts_with("Took "), "{}", printed);
+    assert_eq!(stdout.contents(), "4\n");
+}
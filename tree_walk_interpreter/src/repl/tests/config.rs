@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use crate::error::LoxError;
+use crate::repl::{Dialect, EditMode, ReplConfig};
+
+#[test]
+fn defaults() {
+    let config = ReplConfig::parse("config.toml", "").unwrap();
+    assert_eq!(config, ReplConfig::default());
+    assert_eq!(config.prompt, "> ");
+    assert_eq!(config.edit_mode, EditMode::Emacs);
+    assert!(config.colour);
+}
+
+#[test]
+fn every_setting() {
+    let config = ReplConfig::parse(
+        "config.toml",
+        r#"
+            history_file = "/tmp/lox_history"
+            history_size = 10
+            prompt = "lox> "
+            continuation_prompt = "   | "
+            colour = false
+            edit_mode = "vi"
+            dialect = "lox"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        config,
+        ReplConfig {
+            history_file: Some(PathBuf::from("/tmp/lox_history")),
+            history_size: 10,
+            prompt: "lox> ".to_string(),
+            continuation_prompt: "   | ".to_string(),
+            colour: false,
+            edit_mode: EditMode::Vi,
+            dialect: Dialect::Lox,
+        }
+    );
+    assert_eq!(
+        config.history_path(),
+        Some(PathBuf::from("/tmp/lox_history"))
+    );
+}
+
+#[test]
+fn invalid_settings() {
+    for source in [
+        "edit_mode = \"nano\"",
+        "dialect = \"clox\"",
+        "promt = \"> \"",
+        "colour = 1",
+    ] {
+        assert!(
+            matches!(
+                ReplConfig::parse("config.toml", source),
+                Err(LoxError::InvalidConfig(path, _)) if path == "config.toml"
+            ),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn missing_file_uses_defaults() {
+    let path = std::env::temp_dir().join("darmok_lox_no_such_config.toml");
+    assert_eq!(ReplConfig::load(path).unwrap(), ReplConfig::default());
+}
@@ -0,0 +1,92 @@
+use rustyline::validate::ValidationResult;
+
+use crate::repl::LoxHelper;
+
+#[test]
+fn keyword_completion() {
+    let helper = LoxHelper::new();
+    assert_eq!(helper.completions("pr", 2), (0, vec!["print".to_string()]));
+    assert_eq!(
+        helper.completions("var x = t", 9),
+        (8, vec!["this".to_string(), "true".to_string()])
+    );
+    assert_eq!(helper.completions("print ", 6), (6, vec![]));
+    assert_eq!(helper.completions("print", 5), (0, vec![]));
+    assert_eq!(
+        helper.completions("\"é\" + n", 8),
+        (7, vec!["nil".to_string()])
+    );
+}
+
+#[test]
+fn global_completion() {
+    let mut helper = LoxHelper::new();
+    helper.set_globals(vec![
+        "clock".to_string(),
+        "counter".to_string(),
+        "class_count".to_string(),
+    ]);
+    assert_eq!(
+        helper.completions("print c; c", 10),
+        (
+            9,
+            vec![
+                "class".to_string(),
+                "class_count".to_string(),
+                "clock".to_string(),
+                "counter".to_string()
+            ]
+        )
+    );
+    assert_eq!(
+        helper.completions("counter(cl)", 10),
+        (
+            8,
+            vec![
+                "class".to_string(),
+                "class_count".to_string(),
+                "clock".to_string()
+            ]
+        )
+    );
+}
+
+#[test]
+fn highlighting() {
+    let helper = LoxHelper::new();
+    assert_eq!(
+        helper.highlighted("var a = \"s\" + 1; // c"),
+        "\x1b[1;34mvar\x1b[0m a = \x1b[32m\"s\"\x1b[0m + \x1b[33m1\x1b[0m; \x1b[90m// c\x1b[0m"
+    );
+    assert_eq!(
+        helper.highlighted("print nil;"),
+        "\x1b[1;34mprint\x1b[0m \x1b[35mnil\x1b[0m;"
+    );
+    // Input that doesn't scan is left alone.
+    assert_eq!(helper.highlighted("print \"open"), "print \"open");
+}
+
+#[test]
+fn validation() {
+    let mut helper = LoxHelper::new();
+    assert!(matches!(
+        helper.validation("fun f() {"),
+        ValidationResult::Valid(None)
+    ));
+    match helper.validation("print (1};") {
+        ValidationResult::Invalid(Some(message)) => {
+            assert_eq!(message, "\nUnmatched '}' at 1:9")
+        }
+        _ => panic!("expected the input to be invalid"),
+    }
+
+    helper.set_pending("fun f() {");
+    assert!(matches!(
+        helper.validation("}"),
+        ValidationResult::Valid(None)
+    ));
+    assert!(matches!(
+        helper.validation(")"),
+        ValidationResult::Invalid(Some(_))
+    ));
+}
@@ -0,0 +1,46 @@
+use crate::repl::{input_state, needs_more_input, InputState};
+
+mod commands;
+mod config;
+mod helper;
+
+#[test]
+fn complete_input() {
+    assert!(!needs_more_input(""));
+    assert!(!needs_more_input("print 1;"));
+    assert!(!needs_more_input("fun f() { return (1 + 2); }"));
+    assert!(!needs_more_input("print \"(\"; // {"));
+    assert!(!needs_more_input("/* { */ 1 + 2"));
+}
+
+#[test]
+fn open_brackets() {
+    assert!(needs_more_input("fun f() {"));
+    assert!(needs_more_input(
+        "fun f() {\n  if (true) {\n    print 1;\n  }"
+    ));
+    assert!(needs_more_input("print (1 +"));
+    assert!(needs_more_input("class A {\n  method("));
+}
+
+#[test]
+fn unterminated_strings_and_comments() {
+    assert!(needs_more_input("print \"multi"));
+    assert!(needs_more_input("print \"multi\nline"));
+    assert!(needs_more_input("/* a long\ncomment"));
+}
+
+#[test]
+fn unmatched_brackets() {
+    assert!(!needs_more_input("}"));
+    assert!(!needs_more_input("} {"));
+    match input_state("fun f() {\n  print (1 + 2};") {
+        InputState::UnmatchedBracket(token) => {
+            assert_eq!(
+                (token.lexeme.as_str(), token.line, token.column),
+                ("}", 2, 15)
+            )
+        }
+        state => panic!("unexpected state {:?}", state),
+    }
+}
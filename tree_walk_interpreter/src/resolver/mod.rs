@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use crate::error::{LoxError, ResolverError};
+use crate::parser::ast::{Expr, ExprId, FunctionDeclaration, Stmt};
+use crate::scanner::tokens::LoxToken;
+
+#[cfg(test)]
+mod tests;
+
+/// How many scopes away from the use site each local variable was declared, keyed by the
+/// [ExprId] of the expression using it.  Variables missing from the map are globals.
+pub type Locals = HashMap<ExprId, usize>;
+
+/// Statically resolve every variable reference in the program to the scope it refers to.
+///
+/// This is what makes closures capture the variable that was in scope where they were declared
+/// rather than whatever variable of the same name happens to be around when they are called.
+pub fn resolve(statements: &[Stmt]) -> Result<Locals, Vec<LoxError>> {
+    let mut resolver = Resolver::default();
+    resolver.resolve_statements(statements);
+
+    if resolver.errors.is_empty() {
+        Ok(resolver.locals)
+    } else {
+        Err(resolver.errors)
+    }
+}
+
+/// Like [resolve], for a lone expression such as one typed into the REPL.
+pub fn resolve_expression(expr: &Expr) -> Result<Locals, Vec<LoxError>> {
+    let mut resolver = Resolver::default();
+    resolver.resolve_expression(expr);
+
+    if resolver.errors.is_empty() {
+        Ok(resolver.locals)
+    } else {
+        Err(resolver.errors)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+enum FunctionType {
+    #[default]
+    None,
+    Function,
+    Initializer,
+    Method,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+enum ClassType {
+    #[default]
+    None,
+    Class,
+    Subclass,
+}
+
+#[derive(Default)]
+struct Resolver {
+    /// Stack of block scopes, each mapping a variable name to whether its initializer is done.
+    scopes: Vec<HashMap<String, bool>>,
+    current_function: FunctionType,
+    current_class: ClassType,
+    locals: Locals,
+    errors: Vec<LoxError>,
+}
+
+impl Resolver {
+    fn resolve_statements(&mut self, statements: &[Stmt]) {
+        for statement in statements {
+            self.resolve_statement(statement);
+        }
+    }
+
+    fn resolve_statement(&mut self, statement: &Stmt) {
+        match statement {
+            Stmt::Block(statements) => {
+                self.begin_scope();
+                self.resolve_statements(statements);
+                self.end_scope();
+            }
+            Stmt::Class(declaration) => {
+                let enclosing_class = self.current_class;
+                self.current_class = ClassType::Class;
+
+                self.declare(&declaration.name);
+                self.define(&declaration.name);
+
+                if let Some(superclass @ Expr::Variable { name, .. }) = &declaration.superclass {
+                    if name.lexeme == declaration.name.lexeme {
+                        self.errors.push(
+                            ResolverError::InheritFromSelf(
+                                name.line,
+                                name.column,
+                                name.lexeme.clone(),
+                            )
+                            .into(),
+                        );
+                    }
+                    self.current_class = ClassType::Subclass;
+                    self.resolve_expression(superclass);
+
+                    // Subclass methods get an extra scope holding `super`, mirroring the
+                    // environment the interpreter creates around them.
+                    self.begin_scope();
+                    if let Some(scope) = self.scopes.last_mut() {
+                        scope.insert("super".to_string(), true);
+                    }
+                }
+
+                // Methods are resolved in a scope holding `this`, which is where a bound method's
+                // environment will put the instance at runtime.
+                self.begin_scope();
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.insert("this".to_string(), true);
+                }
+                for method in &declaration.methods {
+                    let function_type = if method.name.lexeme == "init" {
+                        FunctionType::Initializer
+                    } else {
+                        FunctionType::Method
+                    };
+                    self.resolve_function(method, function_type);
+                }
+                self.end_scope();
+
+                if declaration.superclass.is_some() {
+                    self.end_scope();
+                }
+
+                self.current_class = enclosing_class;
+            }
+            Stmt::Expression(expr) | Stmt::Print(expr) => self.resolve_expression(expr),
+            Stmt::Function(declaration) => {
+                // Declared and defined eagerly so the function can refer to itself recursively.
+                self.declare(&declaration.name);
+                self.define(&declaration.name);
+                self.resolve_function(declaration, FunctionType::Function);
+            }
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.resolve_expression(condition);
+                self.resolve_statement(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.resolve_statement(else_branch);
+                }
+            }
+            Stmt::Return { keyword, value } => {
+                if self.current_function == FunctionType::None {
+                    self.errors
+                        .push(ResolverError::TopLevelReturn(keyword.line, keyword.column).into());
+                }
+                if let Some(value) = value {
+                    if self.current_function == FunctionType::Initializer {
+                        self.errors.push(
+                            ResolverError::ReturnValueFromInitializer(keyword.line, keyword.column)
+                                .into(),
+                        );
+                    }
+                    self.resolve_expression(value);
+                }
+            }
+            Stmt::Var { name, initializer } => {
+                self.declare(name);
+                if let Some(initializer) = initializer {
+                    self.resolve_expression(initializer);
+                }
+                self.define(name);
+            }
+            Stmt::While {
+                condition, body, ..
+            } => {
+                self.resolve_expression(condition);
+                self.resolve_statement(body);
+            }
+        }
+    }
+
+    fn resolve_function(&mut self, declaration: &FunctionDeclaration, function_type: FunctionType) {
+        let enclosing_function = self.current_function;
+        self.current_function = function_type;
+
+        self.begin_scope();
+        for param in &declaration.params {
+            self.declare(param);
+            self.define(param);
+        }
+        self.resolve_statements(&declaration.body);
+        self.end_scope();
+
+        self.current_function = enclosing_function;
+    }
+
+    fn resolve_expression(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Assign { id, name, value } => {
+                self.resolve_expression(value);
+                self.resolve_local(*id, name);
+            }
+            Expr::Binary { left, right, .. } | Expr::Logical { left, right, .. } => {
+                self.resolve_expression(left);
+                self.resolve_expression(right);
+            }
+            Expr::Call {
+                callee, arguments, ..
+            } => {
+                self.resolve_expression(callee);
+                for argument in arguments {
+                    self.resolve_expression(argument);
+                }
+            }
+            Expr::Get { object, .. } => self.resolve_expression(object),
+            Expr::Grouping(expr) => self.resolve_expression(expr),
+            Expr::Literal(_) => {}
+            Expr::Set { object, value, .. } => {
+                self.resolve_expression(value);
+                self.resolve_expression(object);
+            }
+            Expr::Super { id, keyword, .. } => {
+                match self.current_class {
+                    ClassType::None => self.errors.push(
+                        ResolverError::SuperOutsideClass(keyword.line, keyword.column).into(),
+                    ),
+                    ClassType::Class => self.errors.push(
+                        ResolverError::SuperWithoutSuperclass(keyword.line, keyword.column).into(),
+                    ),
+                    ClassType::Subclass => {}
+                }
+                self.resolve_local(*id, keyword);
+            }
+            Expr::This { id, keyword } => {
+                if self.current_class == ClassType::None {
+                    self.errors
+                        .push(ResolverError::ThisOutsideClass(keyword.line, keyword.column).into());
+                    return;
+                }
+                self.resolve_local(*id, keyword);
+            }
+            Expr::Unary { right, .. } => self.resolve_expression(right),
+            Expr::Variable { id, name } => {
+                let declared_but_undefined = self
+                    .scopes
+                    .last()
+                    .and_then(|scope| scope.get(&name.lexeme))
+                    .map(|&defined| !defined)
+                    .unwrap_or(false);
+                if declared_but_undefined {
+                    self.errors.push(
+                        ResolverError::ReadInOwnInitializer(
+                            name.line,
+                            name.column,
+                            name.lexeme.clone(),
+                        )
+                        .into(),
+                    );
+                }
+                self.resolve_local(*id, name);
+            }
+        }
+    }
+
+    fn resolve_local(&mut self, id: ExprId, name: &LoxToken) {
+        if let Some(depth) = self
+            .scopes
+            .iter()
+            .rev()
+            .position(|scope| scope.contains_key(&name.lexeme))
+        {
+            self.locals.insert(id, depth);
+        }
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, name: &LoxToken) {
+        if let Some(scope) = self.scopes.last_mut() {
+            if scope.contains_key(&name.lexeme) {
+                self.errors.push(
+                    ResolverError::AlreadyDeclared(name.line, name.column, name.lexeme.clone())
+                        .into(),
+                );
+            }
+            scope.insert(name.lexeme.clone(), false);
+        }
+    }
+
+    fn define(&mut self, name: &LoxToken) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.clone(), true);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::error::{LoxError, ResolverError};
+use crate::parser::ast::{Expr, Stmt};
+use crate::parser::parse;
+use crate::resolver::resolve;
+use crate::scanner::scan;
+
+fn resolve_source(source: &str) -> Result<usize, Vec<LoxError>> {
+    let statements = parse(&scan(source)).unwrap();
+    resolve(&statements).map(|locals| locals.len())
+}
+
+#[test]
+fn globals_are_not_resolved() {
+    assert_eq!(resolve_source("var a = 1; print a;"), Ok(0));
+}
+
+#[test]
+fn closure_distance() {
+    let statements = parse(&scan("fun outer() { var a; fun inner() { print a; } }")).unwrap();
+    let locals = resolve(&statements).unwrap();
+
+    let inner = match &statements[0] {
+        Stmt::Function(outer) => match &outer.body[1] {
+            Stmt::Function(inner) => inner.clone(),
+            s => panic!("unexpected statement {:?}", s),
+        },
+        s => panic!("unexpected statement {:?}", s),
+    };
+    match &inner.body[0] {
+        Stmt::Print(Expr::Variable { id, .. }) => assert_eq!(locals.get(id), Some(&1)),
+        s => panic!("unexpected statement {:?}", s),
+    }
+}
+
+#[test]
+fn read_in_own_initializer() {
+    assert_eq!(
+        resolve_source("{ var a = a; }"),
+        Err(vec![LoxError::ResolverError(
+            ResolverError::ReadInOwnInitializer(1, 11, "a".to_string())
+        )])
+    );
+}
+
+#[test]
+fn already_declared() {
+    assert_eq!(
+        resolve_source("fun f(a) { var a; }"),
+        Err(vec![LoxError::ResolverError(
+            ResolverError::AlreadyDeclared(1, 16, "a".to_string())
+        )])
+    );
+}
+
+#[test]
+fn top_level_return() {
+    assert_eq!(
+        resolve_source("return 1;"),
+        Err(vec![LoxError::ResolverError(
+            ResolverError::TopLevelReturn(1, 1)
+        )])
+    );
+}
+
+#[test]
+fn this_outside_class() {
+    assert_eq!(
+        resolve_source("fun f() { print this; }"),
+        Err(vec![LoxError::ResolverError(
+            ResolverError::ThisOutsideClass(1, 17)
+        )])
+    );
+}
+
+#[test]
+fn return_value_from_initializer() {
+    assert_eq!(
+        resolve_source("class Foo { init() { return 1; } }"),
+        Err(vec![LoxError::ResolverError(
+            ResolverError::ReturnValueFromInitializer(1, 22)
+        )])
+    );
+}
+
+#[test]
+fn bare_return_from_initializer() {
+    assert_eq!(resolve_source("class Foo { init() { return; } }"), Ok(0));
+}
+
+#[test]
+fn inherit_from_self() {
+    assert_eq!(
+        resolve_source("class Foo < Foo {}"),
+        Err(vec![LoxError::ResolverError(
+            ResolverError::InheritFromSelf(1, 13, "Foo".to_string())
+        )])
+    );
+}
+
+#[test]
+fn super_outside_class() {
+    assert_eq!(
+        resolve_source("fun f() { super.foo(); }"),
+        Err(vec![LoxError::ResolverError(
+            ResolverError::SuperOutsideClass(1, 11)
+        )])
+    );
+}
+
+#[test]
+fn super_without_superclass() {
+    assert_eq!(
+        resolve_source("class Foo { bar() { super.bar(); } }"),
+        Err(vec![LoxError::ResolverError(
+            ResolverError::SuperWithoutSuperclass(1, 21)
+        )])
+    );
+}
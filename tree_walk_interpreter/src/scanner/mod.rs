@@ -23,7 +23,7 @@ pub fn scan_with_whitespace(source: &str, remove_whitespace: bool) -> Vec<LoxRes
     if remove_whitespace {
         tokens
             .into_iter()
-            .filter(|tr| !tr.as_ref().map(|t| t.is_whitespace()).unwrap_or(true))
+            .filter(|tr| !tr.as_ref().map(|t| t.is_whitespace()).unwrap_or(false))
             .collect()
     } else {
         tokens
@@ -36,8 +36,6 @@ pub fn scan(source: &str) -> Vec<LoxResult<LoxToken>> {
 }
 
 /// Tokenized representation of Lox source code
-/// A struct that caches the regex expressions for tokenizing
-
 fn tokenize(input: &str) -> Vec<LoxResult<LoxToken>> {
     let mut tokens: Vec<LoxResult<LoxToken>> = Vec::new();
     let mut tokenizer_state = TokenizerState::new(input);
@@ -57,9 +55,7 @@ fn tokenize(input: &str) -> Vec<LoxResult<LoxToken>> {
     tokens
 }
 
-fn tokenize_next<'a, 'b>(
-    state: &'a TokenizerState<'b>,
-) -> (LoxResult<LoxToken>, TokenizerState<'b>) {
+fn tokenize_next<'b>(state: &TokenizerState<'b>) -> (LoxResult<LoxToken>, TokenizerState<'b>) {
     let first = state.remaining.chars().next().unwrap();
     match first {
         // Ignored single characters that are added for linting purposes.
@@ -81,14 +77,13 @@ fn tokenize_next<'a, 'b>(
         '*' => consume_single_char_token!(state, first, Star),
 
         // Single characters that may have more chars
-        '!' => {
+        '!' | '=' | '<' | '>' => {
             let (token, next_state) = consume_ambiguous_single_char_token(state, first);
             (Ok(token), next_state)
         }
         '/' => {
             // Special handling because of comments.
-            let (token, next_state) = consume_lexeme_beginning_with_forward_slash(state);
-            (Ok(token), next_state)
+            consume_lexeme_beginning_with_forward_slash(state)
         }
 
         // Multi char tokens
@@ -98,15 +93,16 @@ fn tokenize_next<'a, 'b>(
         // Keywords/identifiers, require maximal munching.
         c if is_alpha(c) => consume_identifier(state),
 
+        // A character outside of ASCII is several bytes long.
         _ => (
             Err(ScannerError::UnexpectedCharacter(state.line, state.column, first).into()),
-            state.consume_single_char(),
+            state.consume_n_chars(first.len_utf8()),
         ),
     }
 }
 
-fn consume_single_char_token<'a, 'b>(
-    state: &'a TokenizerState<'b>,
+fn consume_single_char_token<'b>(
+    state: &TokenizerState<'b>,
     first: char,
     token_type: TokenType,
 ) -> (LoxToken, TokenizerState<'b>) {
@@ -128,8 +124,8 @@ fn consume_single_char_token<'a, 'b>(
 
 /// Lexemes beginning with ! = < > (and maybe more in the future) are ambiguous and may be
 /// longer than just one char, so we check.
-fn consume_ambiguous_single_char_token<'a, 'b>(
-    state: &'a TokenizerState<'b>,
+fn consume_ambiguous_single_char_token<'b>(
+    state: &TokenizerState<'b>,
     first: char,
 ) -> (LoxToken, TokenizerState<'b>) {
     if !second_char_matches(state, '=') {
@@ -163,74 +159,74 @@ fn consume_ambiguous_single_char_token<'a, 'b>(
     )
 }
 
-fn consume_lexeme_beginning_with_forward_slash<'a, 'b>(
-    state: &'a TokenizerState<'b>,
-) -> (LoxToken, TokenizerState<'b>) {
+fn consume_lexeme_beginning_with_forward_slash<'b>(
+    state: &TokenizerState<'b>,
+) -> (LoxResult<LoxToken>, TokenizerState<'b>) {
     if second_char_matches(state, '/') {
-        return consume_line_comment(state);
+        let (token, next_state) = consume_line_comment(state);
+        return (Ok(token), next_state);
     } else if second_char_matches(state, '*') {
         return consume_block_comment(state);
     }
 
     (
-        LoxToken {
+        Ok(LoxToken {
             token_type: TokenType::Slash,
             lexeme: '/'.to_string(),
             line: state.line,
             column: state.column,
-        },
+        }),
         state.consume_single_char(),
     )
 }
 
-fn consume_line_comment<'a, 'b>(state: &'a TokenizerState<'b>) -> (LoxToken, TokenizerState<'b>) {
-    // This is a line comment
-    let comment_line: String = state.remaining.chars().take_while(|&c| c != '\n').collect();
+fn consume_line_comment<'b>(state: &TokenizerState<'b>) -> (LoxToken, TokenizerState<'b>) {
+    // This is a line comment, the \r of a \r\n line ending is left for the linter.
+    let comment_line = state.remaining.split('\n').next().unwrap_or_default();
+    let comment_line = comment_line.strip_suffix('\r').unwrap_or(comment_line);
     let comment_length = comment_line.len();
-    return (
+    (
         LoxToken {
             token_type: TokenType::Comment,
-            lexeme: comment_line,
+            lexeme: comment_line.to_string(),
             line: state.line,
             column: state.column,
         },
         state.consume_n_chars(comment_length),
-    );
+    )
 }
 
-fn consume_block_comment<'a, 'b>(state: &'a TokenizerState<'b>) -> (LoxToken, TokenizerState<'b>) {
-    let mut comment_block = "/*".to_string();
-    let mut offset = 2usize;
-    loop {
-        comment_block.extend(
-            state
-                .remaining
-                .chars()
-                .skip(offset)
-                .take_while(|&c| c != '/'),
+fn consume_block_comment<'b>(
+    state: &TokenizerState<'b>,
+) -> (LoxResult<LoxToken>, TokenizerState<'b>) {
+    // Block comments don't nest, the first */ ends the comment.
+    let (comment_block, terminated) = match state.remaining[2..].find("*/") {
+        Some(end) => (&state.remaining[..end + 4], true),
+        None => (state.remaining, false),
+    };
+    let chars_to_consume = comment_block.len();
+    let newlines = comment_block.matches('\n').count();
+    let new_column = to_new_column_offset(state, comment_block, chars_to_consume);
+    let next_state = state.consume_n_chars_with_newlines(chars_to_consume, new_column, newlines);
+
+    if !terminated {
+        return (
+            Err(ScannerError::UnterminatedBlockComment(state.line, state.column).into()),
+            next_state,
         );
-        if nth_char_matches(state, comment_block.len() - 1, '*') {
-            comment_block.push('/');
-            let chars_to_consume = comment_block.len();
-            let newlines = comment_block.lines().count() - 1;
-            let new_column = to_new_column_offset(state, &comment_block, chars_to_consume);
-            return (
-                LoxToken {
-                    token_type: TokenType::BlockComment,
-                    lexeme: comment_block,
-                    line: state.line,
-                    column: state.column,
-                },
-                state.consume_n_chars_with_newlines(chars_to_consume, new_column, newlines),
-            );
-        }
-        offset = comment_block.len();
     }
+    (
+        Ok(LoxToken {
+            token_type: TokenType::BlockComment,
+            lexeme: comment_block.to_string(),
+            line: state.line,
+            column: state.column,
+        }),
+        next_state,
+    )
 }
 
-fn consume_string<'a, 'b>(
-    state: &'a TokenizerState<'b>,
-) -> (LoxResult<LoxToken>, TokenizerState<'b>) {
+fn consume_string<'b>(state: &TokenizerState<'b>) -> (LoxResult<LoxToken>, TokenizerState<'b>) {
     let string_without_quotes: String = state
         .remaining
         .chars()
@@ -238,8 +234,8 @@ fn consume_string<'a, 'b>(
         .take_while(|&c| c != '"')
         .collect();
 
-    let num_newlines = string_without_quotes.lines().count() - 1;
-    let string_terminated = nth_char_matches(state, string_without_quotes.len() + 1, '"');
+    let num_newlines = string_without_quotes.matches('\n').count();
+    let string_terminated = nth_char_matches(state, string_without_quotes.chars().count() + 1, '"');
 
     let string = format!("\"{}\"", string_without_quotes);
     let chars_to_consume = string.len();
@@ -278,9 +274,7 @@ fn consume_string<'a, 'b>(
 }
 
 // BONUS: support numbers without 0. such as .5
-fn consume_digit<'a, 'b>(
-    state: &'a TokenizerState<'b>,
-) -> (LoxResult<LoxToken>, TokenizerState<'b>) {
+fn consume_digit<'b>(state: &TokenizerState<'b>) -> (LoxResult<LoxToken>, TokenizerState<'b>) {
     let mut result: String = state
         .remaining
         .chars()
@@ -311,9 +305,7 @@ fn consume_digit<'a, 'b>(
     )
 }
 
-fn consume_identifier<'a, 'b>(
-    state: &'a TokenizerState<'b>,
-) -> (LoxResult<LoxToken>, TokenizerState<'b>) {
+fn consume_identifier<'b>(state: &TokenizerState<'b>) -> (LoxResult<LoxToken>, TokenizerState<'b>) {
     let identifier: String = state
         .remaining
         .chars()
@@ -361,7 +353,7 @@ fn nth_char_matches_fn<F>(state: &TokenizerState, n: usize, f: F) -> bool
 where
     F: FnOnce(char) -> bool,
 {
-    n < state.remaining.len() && f(state.remaining.chars().nth(n).unwrap())
+    state.remaining.chars().nth(n).is_some_and(f)
 }
 
 fn nth_char_matches(state: &TokenizerState, n: usize, ch: char) -> bool {
@@ -380,7 +372,7 @@ struct TokenizerState<'a> {
 }
 
 impl<'a> TokenizerState<'a> {
-    fn new(input: &str) -> TokenizerState {
+    fn new(input: &str) -> TokenizerState<'_> {
         TokenizerState {
             line: 1,
             column: 1,
@@ -418,7 +410,8 @@ impl<'a> TokenizerState<'a> {
     }
 }
 
-static KEYWORDS: phf::Map<&'static str, TokenType> = phf_map! {
+/// Reserved words, which are scanned as their own token type instead of identifiers.
+pub static KEYWORDS: phf::Map<&'static str, TokenType> = phf_map! {
     "and" =>    TokenType::And,
     "class" =>  TokenType::Class,
     "else" =>   TokenType::Else,
@@ -1,3 +1,4 @@
+use crate::error::ScannerError;
 use crate::scanner::scan_with_whitespace;
 use crate::scanner::tokens::{LoxToken, TokenType};
 use crate::LoxResult;
@@ -61,3 +62,59 @@ is a test string*/"#
         ]
     )
 }
+
+#[test]
+fn block_comment_containing_slashes() {
+    let token_types: Vec<TokenType> = scan_with_whitespace("/* a / b **/ / /**/", true)
+        .into_iter()
+        .map(|t| t.unwrap().token_type)
+        .collect();
+    assert_eq!(
+        token_types,
+        vec![
+            TokenType::BlockComment,
+            TokenType::Slash,
+            TokenType::BlockComment,
+            TokenType::Eof
+        ]
+    )
+}
+
+#[test]
+fn unterminated_block_comment() {
+    let results = scan_with_whitespace("1 /*/ 2\n3", true);
+    assert_eq!(
+        results,
+        vec![
+            Ok(LoxToken {
+                token_type: TokenType::Number(1.0),
+                lexeme: "1".to_string(),
+                line: 1,
+                column: 1,
+            }),
+            Err(ScannerError::UnterminatedBlockComment(1, 3).into()),
+            Ok(LoxToken {
+                token_type: TokenType::Eof,
+                lexeme: "".to_string(),
+                line: 2,
+                column: 2,
+            }),
+        ]
+    )
+}
+
+#[test]
+fn comment_before_crlf() {
+    let results = scan_with_whitespace("// comment\r\nx", false);
+    let types: Vec<TokenType> = results.into_iter().map(|t| t.unwrap().token_type).collect();
+    assert_eq!(
+        types,
+        vec![
+            TokenType::Comment,
+            TokenType::CarriageReturn,
+            TokenType::Linefeed,
+            TokenType::Identifier,
+            TokenType::Eof,
+        ]
+    );
+}
@@ -258,3 +258,20 @@ fn non_builtins() {
         ]
     );
 }
+
+#[test]
+fn letters_at_the_end_of_the_alphabet() {
+    let token_types: Vec<TokenType> = scan_with_whitespace("z Zed", false)
+        .into_iter()
+        .map(|t| t.unwrap().token_type)
+        .collect();
+    assert_eq!(
+        token_types,
+        vec![
+            TokenType::Identifier,
+            TokenType::Space,
+            TokenType::Identifier,
+            TokenType::Eof,
+        ]
+    );
+}
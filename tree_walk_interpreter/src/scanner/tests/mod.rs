@@ -1,6 +1,7 @@
 mod comments;
 mod digits;
 mod identifiers;
+mod operators;
 mod print;
 mod token_error;
 mod whitespace;
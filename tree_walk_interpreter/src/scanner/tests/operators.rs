@@ -0,0 +1,52 @@
+use crate::scanner::scan;
+use crate::scanner::tokens::TokenType;
+
+#[test]
+fn comparison_and_equality_operators() {
+    let token_types: Vec<TokenType> = scan("= == ! != < <= > >=")
+        .into_iter()
+        .map(|t| t.unwrap().token_type)
+        .collect();
+    assert_eq!(
+        token_types,
+        vec![
+            TokenType::Equal,
+            TokenType::EqualEqual,
+            TokenType::Bang,
+            TokenType::BangEqual,
+            TokenType::Less,
+            TokenType::LessEqual,
+            TokenType::Greater,
+            TokenType::GreaterEqual,
+            TokenType::Eof,
+        ]
+    )
+}
+
+#[test]
+fn non_ascii_string() {
+    let token_types: Vec<TokenType> = scan("\"A~¶Þॐஃ\";")
+        .into_iter()
+        .map(|t| t.unwrap().token_type)
+        .collect();
+    assert_eq!(
+        token_types,
+        vec![
+            TokenType::String("A~¶Þॐஃ".to_string()),
+            TokenType::Semicolon,
+            TokenType::Eof
+        ]
+    )
+}
+
+#[test]
+fn empty_string() {
+    let token_types: Vec<TokenType> = scan("\"\"")
+        .into_iter()
+        .map(|t| t.unwrap().token_type)
+        .collect();
+    assert_eq!(
+        token_types,
+        vec![TokenType::String("".to_string()), TokenType::Eof]
+    )
+}
@@ -2,8 +2,8 @@ use pretty_assertions::assert_eq;
 
 use crate::error::LoxError;
 use crate::error::ScannerError::{UnexpectedCharacter, UnterminatedString};
-use crate::scanner::scan_with_whitespace;
 use crate::scanner::tokens::{LoxToken, TokenType};
+use crate::scanner::{scan, scan_with_whitespace};
 
 #[test]
 fn unexpected_char() {
@@ -74,3 +74,44 @@ fn unterminated_string() {
         ]
     );
 }
+
+#[test]
+fn errors_are_kept_when_whitespace_is_removed() {
+    let results = scan("1 ~ 2");
+    assert_eq!(
+        results[1],
+        Err(LoxError::ScannerError(UnexpectedCharacter(1, 3, '~')))
+    );
+}
+
+#[test]
+fn unterminated_non_ascii_string() {
+    let results = scan("\"ॐ");
+    assert_eq!(
+        results[0],
+        Err(LoxError::ScannerError(UnterminatedString(1, 1)))
+    );
+}
+
+#[test]
+fn unexpected_multibyte_char() {
+    let results = scan_with_whitespace("é;", false);
+    assert_eq!(
+        results,
+        vec![
+            Err(LoxError::ScannerError(UnexpectedCharacter(1, 1, 'é'))),
+            Ok(LoxToken {
+                token_type: TokenType::Semicolon,
+                lexeme: ";".to_string(),
+                line: 1,
+                column: 3,
+            }),
+            Ok(LoxToken {
+                token_type: TokenType::Eof,
+                lexeme: "".to_string(),
+                line: 1,
+                column: 4,
+            }),
+        ]
+    );
+}
@@ -8,12 +8,17 @@ pub struct LoxToken {
 
 impl LoxToken {
     pub fn is_whitespace(&self) -> bool {
-        match self.token_type {
-            TokenType::Space | TokenType::Linefeed | TokenType::CarriageReturn | TokenType::Tab => {
-                true
-            }
-            _ => false,
-        }
+        matches!(
+            self.token_type,
+            TokenType::Space | TokenType::Linefeed | TokenType::CarriageReturn | TokenType::Tab
+        )
+    }
+
+    pub fn is_comment(&self) -> bool {
+        matches!(
+            self.token_type,
+            TokenType::Comment | TokenType::BlockComment
+        )
     }
 }
 
@@ -0,0 +1,179 @@
+use std::fs::read_to_string;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use crate::error::{LoxError, LoxResult, RuntimeError};
+use crate::interpreter::limits::Limits;
+use crate::interpreter::native::NativeRegistry;
+use crate::interpreter::value::LoxValue;
+use crate::interpreter::{Interpreter, DEFAULT_MAX_CALL_DEPTH};
+use crate::parser::ast::ReplInput;
+use crate::parser::{parse, parse_repl};
+use crate::resolver::{resolve, resolve_expression};
+use crate::scanner::scan;
+use crate::scanner::tokens::LoxToken;
+
+#[cfg(test)]
+mod tests;
+
+/// An interpreter session, everything a script defines stays around for the scripts and calls
+/// that come after it.
+pub struct Lox {
+    interpreter: Interpreter,
+    /// Where [report](Lox::report) writes diagnostics to.
+    stderr: Box<dyn Write>,
+}
+
+impl Default for Lox {
+    fn default() -> Self {
+        Lox::new()
+    }
+}
+
+impl Lox {
+    /// A session with the standard native functions defined.
+    pub fn new() -> Lox {
+        Lox::with_max_call_depth(DEFAULT_MAX_CALL_DEPTH)
+    }
+
+    /// A session that raises a stack overflow error once more than `max_call_depth` Lox calls are
+    /// nested.
+    pub fn with_max_call_depth(max_call_depth: usize) -> Lox {
+        Lox {
+            interpreter: Interpreter::with_max_call_depth(max_call_depth),
+            stderr: Box::new(std::io::stderr()),
+        }
+    }
+
+    /// Sends the output of `print` statements to `stdout` instead of the process' standard output.
+    pub fn set_stdout(&mut self, stdout: impl Write + 'static) {
+        self.interpreter.set_stdout(stdout);
+    }
+
+    /// Sends [reported](Lox::report) diagnostics to `stderr` instead of the process' standard
+    /// error.
+    pub fn set_stderr(&mut self, stderr: impl Write + 'static) {
+        self.stderr = Box::new(stderr);
+    }
+
+    /// Stops every script or call that exceeds one of the limits with a
+    /// [LimitExceeded](crate::error::RuntimeError::LimitExceeded) error.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.interpreter.set_limits(limits);
+    }
+
+    /// A flag that stops the running script with an
+    /// [Interrupted](crate::error::RuntimeError::Interrupted) error once set.  It is checked
+    /// whenever a loop goes around or a function is called, and cleared when the next script
+    /// starts, so an interrupt that arrives between scripts is dropped.
+    pub fn interrupt_handle(&self) -> Arc<AtomicBool> {
+        self.interpreter.interrupt_handle()
+    }
+
+    /// Uses an existing interrupt flag, so that one handle can interrupt several sessions.
+    pub fn set_interrupt_handle(&mut self, interrupt: Arc<AtomicBool>) {
+        self.interpreter.set_interrupt_handle(interrupt);
+    }
+
+    /// Writes an error (or lint) returned by this session to its standard error.
+    pub fn report(&mut self, error: &LoxError) {
+        let _ = writeln!(self.stderr, "{}", error);
+    }
+
+    /// Runs a script.  If it doesn't compile nothing is run and every error is returned as
+    /// [LoxError::CompileErrors], a runtime error stops the script where it happened.
+    pub fn eval(&mut self, source: &str) -> LoxResult<()> {
+        let statements = scan_and_parse(source, parse)?;
+        let locals = resolve(&statements).map_err(LoxError::CompileErrors)?;
+        self.interpreter.interpret(&statements, locals)
+    }
+
+    /// Runs a line typed into the REPL, like [eval](Lox::eval) except that a bare expression
+    /// (without a trailing `;`) is evaluated and its value returned so it can be printed.
+    pub fn eval_line(&mut self, line: &str) -> LoxResult<Option<LoxValue>> {
+        match scan_and_parse(line, parse_repl)? {
+            ReplInput::Expression(expr) => {
+                let locals = resolve_expression(&expr).map_err(LoxError::CompileErrors)?;
+                self.interpreter
+                    .interpret_expression(&expr, locals)
+                    .map(Some)
+            }
+            ReplInput::Statements(statements) => {
+                let locals = resolve(&statements).map_err(LoxError::CompileErrors)?;
+                self.interpreter
+                    .interpret(&statements, locals)
+                    .map(|()| None)
+            }
+        }
+    }
+
+    /// Reads and runs the script at `path`, see [eval](Lox::eval).
+    pub fn run_file(&mut self, path: impl AsRef<Path>) -> LoxResult<()> {
+        let path = path.as_ref();
+        let source = read_to_string(path)
+            .map_err(|e| LoxError::IoError(path.display().to_string(), e.to_string()))?;
+        self.eval(&source)
+    }
+
+    /// Defines (or redefines) a global variable for the scripts run afterwards.
+    pub fn define_global(&mut self, name: &str, value: impl Into<LoxValue>) {
+        self.interpreter.define_global(name, value.into());
+    }
+
+    /// Defines every function in the registry as a global.
+    pub fn define_natives(&mut self, registry: &NativeRegistry) {
+        self.interpreter.define_natives(registry);
+    }
+
+    /// Forgets everything previous scripts defined, including globals and natives defined by the
+    /// host, leaving only the standard native functions.
+    pub fn reset(&mut self) {
+        self.interpreter.reset();
+    }
+
+    /// Every global variable sorted by name, including the native functions.
+    pub fn globals(&self) -> Vec<(String, LoxValue)> {
+        self.interpreter.globals()
+    }
+
+    pub fn global(&self, name: &str) -> Option<LoxValue> {
+        self.interpreter.global(name)
+    }
+
+    /// Calls the global function (or class) named `function_name` and returns its result.
+    pub fn call(&mut self, function_name: &str, arguments: Vec<LoxValue>) -> LoxResult<LoxValue> {
+        let function = self.interpreter.global(function_name).ok_or_else(|| {
+            LoxError::from(RuntimeError::UndefinedVariable(
+                0,
+                0,
+                function_name.to_string(),
+            ))
+        })?;
+        self.interpreter.call_value(function, arguments)
+    }
+}
+
+/// Scans and parses the source with the given parser, collecting the errors of both.
+pub(crate) fn scan_and_parse<T>(
+    source: &str,
+    parse: impl FnOnce(&[LoxResult<LoxToken>]) -> Result<T, Vec<LoxError>>,
+) -> LoxResult<T> {
+    let tokens = scan(source);
+    let mut errors: Vec<LoxError> = tokens
+        .iter()
+        .filter_map(|t| t.as_ref().err())
+        .cloned()
+        .collect();
+
+    // The parser skips tokens that failed to scan, so it can still report its own errors.
+    match parse(&tokens) {
+        Ok(parsed) if errors.is_empty() => Ok(parsed),
+        Ok(_) => Err(LoxError::CompileErrors(errors)),
+        Err(parser_errors) => {
+            errors.extend(parser_errors);
+            Err(LoxError::CompileErrors(errors))
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Runs the scripts under `lox/test`, checking what they print against the `// expect: ...`
+//! comments in them, the same way the reference implementation's test runner does.
+
+use std::fs::{read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::error::LoxError;
+use crate::{CapturedOutput, Lox};
+
+/// These test intermediate stages of the reference implementation (scanning and printing
+/// expressions) or are too slow to run unoptimized.
+const SKIPPED_DIRECTORIES: [&str; 3] = ["benchmark", "expressions", "scanning"];
+
+/// Limits of the reference bytecode implementation that a tree walker doesn't have.
+const SKIPPED_SCRIPTS: [&str; 5] = [
+    "loop_too_large.lox",
+    "no_reuse_constants.lox",
+    "too_many_constants.lox",
+    "too_many_locals.lox",
+    "too_many_upvalues.lox",
+];
+
+/// What a test script says should happen when it is run.
+#[derive(Debug, Default)]
+struct Expectations {
+    output: Vec<String>,
+    /// Line the error is expected on and its message.
+    runtime_error: Option<(usize, String)>,
+    /// Line and `at '...': message` part of each compile error.
+    compile_errors: Vec<(usize, String)>,
+}
+
+impl Expectations {
+    fn parse(source: &str) -> Expectations {
+        let expect = Regex::new(r"// expect: ?(.*)").unwrap();
+        let runtime_error = Regex::new(r"// expect runtime error: (.+)").unwrap();
+        let compile_error = Regex::new(r"// (?:\[(?:java )?line (\d+)\] )?Error(.*)").unwrap();
+
+        let mut expectations = Expectations::default();
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            if let Some(captures) = expect.captures(line) {
+                expectations.output.push(captures[1].to_string());
+            } else if let Some(captures) = runtime_error.captures(line) {
+                expectations.runtime_error = Some((line_number, captures[1].to_string()));
+            } else if let Some(captures) = compile_error.captures(line) {
+                let line_number = captures
+                    .get(1)
+                    .map_or(line_number, |n| n.as_str().parse().unwrap());
+                expectations
+                    .compile_errors
+                    .push((line_number, captures[2].to_string()));
+            }
+        }
+        expectations
+    }
+}
+
+/// Runs the script and describes how it didn't meet its expectations, if it didn't.
+fn check(path: &Path) -> Option<String> {
+    let source = read_to_string(path).unwrap();
+    let expectations = Expectations::parse(&source);
+
+    let stdout = CapturedOutput::new();
+    let stderr = CapturedOutput::new();
+    let mut lox = Lox::new();
+    lox.set_stdout(stdout.clone());
+    lox.set_stderr(stderr.clone());
+    let result = lox.eval(&source);
+    if let Err(error) = &result {
+        lox.report(error);
+    }
+
+    let output: Vec<String> = stdout.contents().lines().map(str::to_string).collect();
+    if output != expectations.output {
+        return Some(format!(
+            "expected output {:?} but got {:?}",
+            expectations.output, output
+        ));
+    }
+
+    let stderr = stderr.contents();
+    match (&result, &expectations) {
+        (
+            Ok(()),
+            Expectations {
+                runtime_error: None,
+                compile_errors,
+                ..
+            },
+        ) if compile_errors.is_empty() => None,
+        (
+            Err(LoxError::RuntimeError(..)),
+            Expectations {
+                runtime_error: Some((line, message)),
+                ..
+            },
+        ) => {
+            let expected = format!("{}\n[line {}]", message, line);
+            (!stderr.starts_with(&expected))
+                .then(|| format!("expected runtime error {:?} but got {:?}", expected, stderr))
+        }
+        (Err(LoxError::CompileErrors(errors)), Expectations { compile_errors, .. })
+            if !compile_errors.is_empty() =>
+        {
+            check_compile_errors(errors, compile_errors)
+        }
+        _ => Some(format!("expected {:?} but got {:?}", expectations, stderr)),
+    }
+}
+
+/// Our messages have a column where the reference implementation's only have a line, so only the
+/// line and the rest of the message are compared.
+fn check_compile_errors(errors: &[LoxError], expected: &[(usize, String)]) -> Option<String> {
+    let position = Regex::new(r"^Error at (\d+):\d+(.*)$").unwrap();
+    let actual: Vec<(usize, String)> = errors
+        .iter()
+        .filter_map(|error| match error {
+            // Scanner errors are worded differently, they are only checked to have happened.
+            LoxError::ScannerError(_) => None,
+            error => {
+                let message = error.to_string();
+                let captures = position.captures(&message)?;
+                Some((captures[1].parse().unwrap(), captures[2].to_string()))
+            }
+        })
+        .collect();
+    let expected: Vec<(usize, String)> = expected
+        .iter()
+        .filter(|(_, message)| !message.starts_with(':'))
+        .cloned()
+        .collect();
+
+    (actual != expected).then(|| {
+        format!(
+            "expected compile errors {:?} but got {:?}",
+            expected, actual
+        )
+    })
+}
+
+fn lox_scripts(directory: &Path, scripts: &mut Vec<PathBuf>) {
+    for entry in read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            let name = path.file_name().unwrap().to_str().unwrap();
+            if !SKIPPED_DIRECTORIES.contains(&name) {
+                lox_scripts(&path, scripts);
+            }
+        } else if path.extension().is_some_and(|extension| extension == "lox")
+            && !SKIPPED_SCRIPTS.contains(&path.file_name().unwrap().to_str().unwrap())
+        {
+            scripts.push(path);
+        }
+    }
+}
+
+#[test]
+fn golden_scripts() {
+    let test_directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("../lox/test");
+    let mut scripts = Vec::new();
+    lox_scripts(&test_directory, &mut scripts);
+    scripts.sort();
+    assert!(!scripts.is_empty(), "no scripts in {:?}", test_directory);
+
+    let failures: Vec<String> = scripts
+        .iter()
+        .filter_map(|script| {
+            check(script).map(|failure| {
+                let name = script.strip_prefix(&test_directory).unwrap();
+                format!("{}: {}", name.display(), failure)
+            })
+        })
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "{} of {} scripts failed:\n{}",
+        failures.len(),
+        scripts.len(),
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,210 @@
+use std::io::Write;
+
+use crate::error::{LoxError, ParserError, RuntimeError, StackFrame, StackTrace};
+use crate::interpreter::value::LoxValue;
+use crate::{CapturedOutput, Lox, NativeRegistry};
+
+mod golden;
+
+#[test]
+fn globals_persist_between_scripts() {
+    let mut lox = Lox::new();
+    lox.eval("var a = 1;").unwrap();
+    lox.eval("fun add(b) { return a + b; }").unwrap();
+    lox.eval("var c = add(2);").unwrap();
+    assert_eq!(lox.global("c"), Some(LoxValue::Number(3.0)));
+}
+
+#[test]
+fn host_defined_globals() {
+    let mut lox = Lox::new();
+    lox.define_global("name", "world");
+    lox.define_global("count", 2.0);
+    lox.eval(r#"var greeting = "hello " + name; var twice = count * 2;"#)
+        .unwrap();
+    assert_eq!(lox.global("greeting"), Some(LoxValue::from("hello world")));
+    assert_eq!(lox.global("twice"), Some(LoxValue::Number(4.0)));
+}
+
+#[test]
+fn host_defined_natives() {
+    let mut lox = Lox::new();
+    let mut registry = NativeRegistry::new();
+    registry.register_fn("double", |n: f64| n * 2.0);
+    lox.define_natives(&registry);
+    lox.eval("var four = double(2);").unwrap();
+    assert_eq!(lox.global("four"), Some(LoxValue::Number(4.0)));
+}
+
+#[test]
+fn calling_functions() {
+    let mut lox = Lox::new();
+    lox.eval(
+        r#"
+fun fib(n) { if (n < 2) return n; return fib(n - 1) + fib(n - 2); }
+class Point { init(x, y) { this.x = x; this.y = y; } }
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        lox.call("fib", vec![LoxValue::Number(10.0)]),
+        Ok(LoxValue::Number(55.0))
+    );
+    assert_eq!(
+        lox.call("clock", vec![]).map(|v| v.to_string().is_empty()),
+        Ok(false)
+    );
+    let point = lox
+        .call("Point", vec![LoxValue::Number(1.0), LoxValue::Number(2.0)])
+        .unwrap();
+    assert_eq!(point.to_string(), "Point instance");
+}
+
+#[test]
+fn calling_errors() {
+    let mut lox = Lox::new();
+    lox.eval("fun fail() { return nil + 1; } var notAFunction = 1;")
+        .unwrap();
+
+    assert_eq!(
+        lox.call("missing", vec![]),
+        Err(RuntimeError::UndefinedVariable(0, 0, "missing".to_string()).into())
+    );
+    assert!(matches!(
+        lox.call("notAFunction", vec![]),
+        Err(LoxError::RuntimeError(RuntimeError::NotCallable(0, 0), _))
+    ));
+    assert!(matches!(
+        lox.call("fail", vec![LoxValue::Nil]),
+        Err(LoxError::RuntimeError(
+            RuntimeError::ArityMismatch(0, 0, 0, 1),
+            _
+        ))
+    ));
+    assert_eq!(
+        lox.call("fail", vec![]),
+        Err(LoxError::RuntimeError(
+            RuntimeError::OperandsMustBeNumbersOrStrings(1, 25),
+            StackTrace(vec![
+                StackFrame {
+                    function: Some("fail".to_string()),
+                    line: 1
+                },
+                StackFrame {
+                    function: None,
+                    line: 0
+                },
+            ])
+        ))
+    );
+}
+
+#[test]
+fn compile_errors_are_all_reported() {
+    let mut lox = Lox::new();
+    let error = lox.eval("var a = ;\nvar b = ;\nvar c = 1;").unwrap_err();
+    assert!(error.is_compile_error());
+    assert_eq!(
+        error,
+        LoxError::CompileErrors(vec![
+            ParserError::UnexpectedToken(1, 9, ";".to_string(), "Expect expression.".to_string())
+                .into(),
+            ParserError::UnexpectedToken(2, 9, ";".to_string(), "Expect expression.".to_string())
+                .into(),
+        ])
+    );
+    // Nothing runs when the script doesn't compile.
+    assert_eq!(lox.global("c"), None);
+}
+
+#[test]
+fn runtime_errors_keep_earlier_definitions() {
+    let mut lox = Lox::new();
+    let error = lox
+        .eval("var a = 1;\nvar b = -\"b\";\nvar c = 3;")
+        .unwrap_err();
+    assert!(!error.is_compile_error());
+    assert_eq!(lox.global("a"), Some(LoxValue::Number(1.0)));
+    assert_eq!(lox.global("c"), None);
+}
+
+#[test]
+fn running_files() {
+    let path = std::env::temp_dir().join(format!("darmok_lox_{}.lox", std::process::id()));
+    std::fs::File::create(&path)
+        .and_then(|mut file| file.write_all(b"var fromFile = \"loaded\";"))
+        .unwrap();
+
+    let mut lox = Lox::new();
+    let result = lox.run_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    result.unwrap();
+    assert_eq!(lox.global("fromFile"), Some(LoxValue::from("loaded")));
+
+    assert!(matches!(
+        lox.run_file(&path),
+        Err(LoxError::IoError(file, _)) if file == path.display().to_string()
+    ));
+}
+
+#[test]
+fn captured_output() {
+    let stdout = CapturedOutput::new();
+    let stderr = CapturedOutput::new();
+    let mut lox = Lox::new();
+    lox.set_stdout(stdout.clone());
+    lox.set_stderr(stderr.clone());
+
+    lox.eval("print 1 + 2; print \"three\";").unwrap();
+    let error = lox.eval("print nil + 1;").unwrap_err();
+    lox.report(&error);
+
+    assert_eq!(stdout.contents(), "3\nthree\n");
+    assert_eq!(
+        stderr.contents(),
+        "Operands must be two numbers or two strings.\n[line 1] in script\n"
+    );
+
+    stdout.clear();
+    lox.eval("print 4;").unwrap();
+    assert_eq!(stdout.contents(), "4\n");
+}
+
+#[test]
+fn failed_lines_keep_the_session() {
+    let stdout = CapturedOutput::new();
+    let mut lox = Lox::new();
+    lox.set_stdout(stdout.clone());
+
+    let lines = [
+        "var count = 0;",
+        "fun increment() { count = count + 1; return count; }",
+        "class Counter { init() { this.total = increment(); } }",
+        "print undefined;",
+        "var = ;",
+        "print Counter().total;",
+        "var count = 10;",
+        "print increment();",
+    ];
+    let failures = lines.iter().filter(|line| lox.eval(line).is_err()).count();
+
+    assert_eq!(failures, 2);
+    assert_eq!(stdout.contents(), "1\n11\n");
+}
+
+#[test]
+fn repl_lines() {
+    let stdout = CapturedOutput::new();
+    let mut lox = Lox::new();
+    lox.set_stdout(stdout.clone());
+
+    assert_eq!(lox.eval_line("1 + 2"), Ok(Some(LoxValue::Number(3.0))));
+    assert_eq!(lox.eval_line("var a = \"a\";"), Ok(None));
+    assert_eq!(lox.eval_line("a + \"b\""), Ok(Some(LoxValue::from("ab"))));
+    assert_eq!(lox.eval_line("a = \"c\""), Ok(Some(LoxValue::from("c"))));
+    assert_eq!(lox.eval_line("print a;"), Ok(None));
+    assert!(lox.eval_line("-a").is_err());
+    assert!(lox.eval_line("1 +").unwrap_err().is_compile_error());
+    assert_eq!(stdout.contents(), "c\n");
+}
@@ -1,6 +1,28 @@
-use crate::error::ScannerError;
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+use crate::error::{LoxError, ScannerError};
 use crate::LoxResult;
 
+/// Reads the TOML config at `path`, a missing file just means every setting has its default.
+pub fn load_toml<T: DeserializeOwned + Default>(path: impl AsRef<Path>) -> LoxResult<T> {
+    let path = path.as_ref();
+    match read_to_string(path) {
+        Ok(source) => parse_toml(path, &source),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(LoxError::IoError(path.display().to_string(), e.to_string())),
+    }
+}
+
+/// Parses the contents of a TOML config, `path` is only used in error messages.
+pub fn parse_toml<T: DeserializeOwned>(path: impl AsRef<Path>, source: &str) -> LoxResult<T> {
+    toml::from_str(source)
+        .map_err(|e| LoxError::InvalidConfig(path.as_ref().display().to_string(), e.to_string()))
+}
+
 // BONUS: unicode values \u
 /// Unescapes a string for \, ", \n, and \t
 pub fn unescape_string(string: &str, lineno: usize, columno: usize) -> LoxResult<String> {
@@ -38,11 +60,11 @@ pub fn unescape_string(string: &str, lineno: usize, columno: usize) -> LoxResult
 }
 
 pub fn is_digit(d: char) -> bool {
-    ('0'..='9').contains(&d)
+    d.is_ascii_digit()
 }
 
 pub fn is_alpha(c: char) -> bool {
-    ('a'..'z').contains(&c) || ('A'..'Z').contains(&c) || (c == '_')
+    c.is_ascii_alphabetic() || (c == '_')
 }
 
 pub fn is_alpha_numeric(c: char) -> bool {